@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use material_color_utilities::palettes::{CorePalette, TonalPalette};
+
+fn tonal_palette(c: &mut Criterion) {
+    c.bench_function("tonal palette, first lookup of every tone", |b| {
+        b.iter(|| {
+            let palette = TonalPalette::from_int(black_box(0xff4285f4));
+            (0..=100).map(|tone| palette.tone(tone)).sum::<u32>()
+        })
+    });
+
+    let palette = TonalPalette::from_int(0xff4285f4);
+    (0..=100).for_each(|tone| {
+        palette.tone(tone);
+    });
+    c.bench_function("tonal palette, cached lookup of every tone", |b| {
+        b.iter(|| {
+            (0..=100)
+                .map(|tone| palette.tone(black_box(tone)))
+                .sum::<u32>()
+        })
+    });
+
+    c.bench_function("core palette, creation", |b| {
+        b.iter(|| CorePalette::of(black_box(0xff4285f4)))
+    });
+}
+
+criterion_group!(benches, tonal_palette);
+criterion_main!(benches);
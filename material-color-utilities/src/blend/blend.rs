@@ -0,0 +1,136 @@
+use crate::{
+    hct::{Cam16, Hct},
+    utils::{
+        color_utils::lstar_from_argb,
+        math_utils::{difference_degrees, rotation_direction, sanitize_degrees_double},
+    },
+};
+
+/// Functions for blending in HCT and CAM16.
+pub struct Blend;
+
+impl Blend {
+    /// Blend the design color's HCT hue towards the key color's HCT hue, in a
+    /// way that leaves the original color recognizable and recognizably
+    /// shifted towards the key color.
+    ///
+    /// # Arguments
+    ///
+    /// * `design_color` - ARGB representation of an arbitrary color.
+    /// * `source_color` - ARGB representation of the main theme color.
+    ///
+    /// # Returns
+    ///
+    /// The design color with a hue shifted towards the system's color, a
+    /// slightly warmer/cooler variant of the design color's hue.
+    pub fn harmonize(design_color: u32, source_color: u32) -> u32 {
+        let from_hct = Hct::from_int(design_color);
+        let to_hct = Hct::from_int(source_color);
+        let difference_degrees = difference_degrees(from_hct.hue(), to_hct.hue());
+        let rotation_degrees = (difference_degrees * 0.5).min(15.0);
+        let output_hue = sanitize_degrees_double(
+            from_hct.hue() + rotation_degrees * rotation_direction(from_hct.hue(), to_hct.hue()),
+        );
+        Hct::from_hct(output_hue, from_hct.chroma(), from_hct.tone()).to_int()
+    }
+
+    /// Blends hue from one color into another. The chroma and tone of the
+    /// original color are maintained.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - ARGB representation of color
+    /// * `to` - ARGB representation of color
+    /// * `amount` - how much blending to perform; 0.0 >= and <= 1.0
+    ///
+    /// # Returns
+    ///
+    /// `from`, with a hue blended towards `to`. Chroma and tone are constant.
+    pub fn hct_hue(from: u32, to: u32, amount: f64) -> u32 {
+        let ucs = Blend::cam16_ucs(from, to, amount);
+        let ucs_cam = Cam16::from_int(ucs);
+        let from_cam = Cam16::from_int(from);
+        let blended = Hct::from_hct(ucs_cam.hue, from_cam.chroma, lstar_from_argb(from));
+        blended.to_int()
+    }
+
+    /// Blend in CAM16-UCS space.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - ARGB representation of color
+    /// * `to` - ARGB representation of color
+    /// * `amount` - how much blending to perform; 0.0 >= and <= 1.0
+    ///
+    /// # Returns
+    ///
+    /// `from`, blended towards `to`. Hue, chroma, and tone will change.
+    pub fn cam16_ucs(from: u32, to: u32, amount: f64) -> u32 {
+        let from_cam = Cam16::from_int(from);
+        let to_cam = Cam16::from_int(to);
+        let from_j = from_cam.j_star;
+        let from_a = from_cam.a_star;
+        let from_b = from_cam.b_star;
+        let to_j = to_cam.j_star;
+        let to_a = to_cam.a_star;
+        let to_b = to_cam.b_star;
+        let j_star = from_j + (to_j - from_j) * amount;
+        let a_star = from_a + (to_a - from_a) * amount;
+        let b_star = from_b + (to_b - from_b) * amount;
+        Cam16::from_ucs(j_star, a_star, b_star).to_int()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xffff0000;
+    const BLUE: u32 = 0xff0000ff;
+    const GREEN: u32 = 0xff00ff00;
+    const YELLOW: u32 = 0xffffff00;
+
+    #[test]
+    fn harmonize_matches_upstream() {
+        let cases = [
+            (RED, BLUE, 0xfffb0057),
+            (RED, GREEN, 0xffd85600),
+            (RED, YELLOW, 0xffd85600),
+            (BLUE, GREEN, 0xff0047a3),
+            (BLUE, RED, 0xff5700dc),
+            (BLUE, YELLOW, 0xff0047a3),
+            (GREEN, BLUE, 0xff00fc94),
+            (GREEN, RED, 0xffb1f000),
+            (GREEN, YELLOW, 0xffb1f000),
+            (YELLOW, BLUE, 0xffebffba),
+            (YELLOW, GREEN, 0xffebffba),
+            (YELLOW, RED, 0xfffff6e3),
+        ];
+        for (design_color, source_color, expected) in cases {
+            assert_eq!(
+                Blend::harmonize(design_color, source_color),
+                expected,
+                "{:08x} towards {:08x}",
+                design_color,
+                source_color
+            );
+        }
+    }
+
+    #[test]
+    fn hct_hue_keeps_tone() {
+        assert_eq!(Blend::hct_hue(RED, BLUE, 0.0), RED);
+        assert_eq!(Blend::hct_hue(RED, BLUE, 0.5), 0xffe700c9);
+
+        let blended = Hct::from_int(Blend::hct_hue(RED, BLUE, 1.0));
+        assert!(difference_degrees(blended.hue(), Hct::from_int(BLUE).hue()) < 1.0);
+        assert!((blended.tone() - Hct::from_int(RED).tone()).abs() < 1.0);
+    }
+
+    #[test]
+    fn cam16_ucs_blends_every_dimension() {
+        assert_eq!(Blend::cam16_ucs(RED, BLUE, 0.0), RED);
+        assert_eq!(Blend::cam16_ucs(RED, BLUE, 0.5), 0xff9a4a86);
+        assert_eq!(Blend::cam16_ucs(RED, BLUE, 1.0), BLUE);
+    }
+}
@@ -0,0 +1,3 @@
+mod blend;
+
+pub use blend::*;
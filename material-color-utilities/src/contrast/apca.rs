@@ -0,0 +1,410 @@
+use crate::{
+    palettes::TonalPalette,
+    utils::{
+        color_utils::{blue_from_argb, green_from_argb, red_from_argb, y_from_lstar},
+        math_utils::lerp,
+    },
+};
+
+// Constants of the APCA-W3 0.0.98G-4g base algorithm, used by the WCAG 3
+// working draft.
+const MAIN_TRC: f64 = 2.4;
+const S_R_CO: f64 = 0.2126729;
+const S_G_CO: f64 = 0.7151522;
+const S_B_CO: f64 = 0.0721750;
+
+const NORM_BG: f64 = 0.56;
+const NORM_TXT: f64 = 0.57;
+const REV_TXT: f64 = 0.62;
+const REV_BG: f64 = 0.65;
+
+const BLK_THRS: f64 = 0.022;
+const BLK_CLMP: f64 = 1.414;
+const SCALE_BOW: f64 = 1.14;
+const SCALE_WOB: f64 = 1.14;
+const LO_BOW_OFFSET: f64 = 0.027;
+const LO_WOB_OFFSET: f64 = 0.027;
+const DELTA_Y_MIN: f64 = 0.0005;
+const LO_CLIP: f64 = 0.1;
+
+/// WCAG 2 contrast ratios and the APCA Lc values that serve the same use
+/// cases: 3.0 for large text ~ Lc 45, 4.5 for body text ~ Lc 60, 7.0 for
+/// enhanced body text ~ Lc 75. 21.0 maps to the highest Lc APCA produces for
+/// dark text on a light background.
+const RATIO_TO_LC: [(f64, f64); 7] = [
+    (1.0, 0.0),
+    (1.5, 15.0),
+    (3.0, 45.0),
+    (4.5, 60.0),
+    (7.0, 75.0),
+    (11.0, 90.0),
+    (21.0, 106.0),
+];
+
+/// The number of bisection steps used when solving for a tone. Enough to get
+/// well below a hundredth of a tone.
+pub(crate) const SOLVER_ITERATIONS: usize = 32;
+
+/// The APCA luminance estimate of an sRGB component, from 0 to 255.
+fn apca_channel(component: u8) -> f64 {
+    (component as f64 / 255.0).powf(MAIN_TRC)
+}
+
+/// The APCA luminance estimate of a color. Alpha is ignored.
+fn apca_y_from_argb(argb: u32) -> f64 {
+    S_R_CO * apca_channel(red_from_argb(argb))
+        + S_G_CO * apca_channel(green_from_argb(argb))
+        + S_B_CO * apca_channel(blue_from_argb(argb))
+}
+
+/// The APCA luminance estimate of a gray with the given tone.
+fn apca_y_from_tone(tone: f64) -> f64 {
+    let y = y_from_lstar(tone) / 100.0;
+    let encoded = if y <= 0.0031308 {
+        12.92 * y
+    } else {
+        1.055 * y.powf(1.0 / 2.4) - 0.055
+    };
+    encoded.clamp(0.0, 1.0).powf(MAIN_TRC)
+}
+
+/// APCA lightness contrast (Lc) of two luminance estimates.
+fn apca_contrast_of_ys(text_y: f64, background_y: f64) -> f64 {
+    // Soft clamp the luminance of very dark colors, to account for flare.
+    let soft_clamp = |y: f64| {
+        if y > BLK_THRS {
+            y
+        } else {
+            y + (BLK_THRS - y).powf(BLK_CLMP)
+        }
+    };
+    let text_y = soft_clamp(text_y);
+    let background_y = soft_clamp(background_y);
+
+    if (background_y - text_y).abs() < DELTA_Y_MIN {
+        return 0.0;
+    }
+
+    let output = if background_y > text_y {
+        // Dark text on a light background.
+        let sapc = (background_y.powf(NORM_BG) - text_y.powf(NORM_TXT)) * SCALE_BOW;
+        if sapc < LO_CLIP {
+            0.0
+        } else {
+            sapc - LO_BOW_OFFSET
+        }
+    } else {
+        // Light text on a dark background.
+        let sapc = (background_y.powf(REV_BG) - text_y.powf(REV_TXT)) * SCALE_WOB;
+        if sapc > -LO_CLIP {
+            0.0
+        } else {
+            sapc + LO_WOB_OFFSET
+        }
+    };
+    output * 100.0
+}
+
+/// APCA lightness contrast (Lc) of text on a background, as defined by the
+/// WCAG 3 working draft.
+///
+/// Unlike a WCAG 2 contrast ratio, APCA is not symmetric: it models that
+/// light text on a dark background reads differently from dark text on a
+/// light background.
+///
+/// # Arguments
+///
+/// * `text_argb` - ARGB representation of the text, or foreground, color.
+/// * `background_argb` - ARGB representation of the background color.
+///
+/// # Returns
+///
+/// Lc, from about -108 to 106. Positive for dark text on a light background,
+/// negative for light text on a dark background, 0 when the colors are too
+/// close to be legible.
+pub fn apca_contrast(text_argb: u32, background_argb: u32) -> f64 {
+    apca_contrast_of_ys(
+        apca_y_from_argb(text_argb),
+        apca_y_from_argb(background_argb),
+    )
+}
+
+/// APCA lightness contrast (Lc) of two tones. T in HCT, L* in L*a*b*.
+///
+/// The tones are treated as grays, so the result is an estimate for colors
+/// with chroma.
+///
+/// # Arguments
+///
+/// * `text_tone` - Tone of the text, or foreground, from 0 to 100.
+/// * `background_tone` - Tone of the background, from 0 to 100.
+///
+/// # Returns
+///
+/// Lc, from about -108 to 106. Positive for dark text on a light background,
+/// negative for light text on a dark background.
+pub fn apca_contrast_of_tones(text_tone: f64, background_tone: f64) -> f64 {
+    apca_contrast_of_ys(
+        apca_y_from_tone(text_tone),
+        apca_y_from_tone(background_tone),
+    )
+}
+
+/// Returns the APCA Lc that serves the same purpose as a WCAG 2 contrast
+/// ratio, e.g. Lc 60 for 4.5:1 and Lc 75 for 7:1.
+///
+/// APCA does not specify a mapping from WCAG 2 ratios. This is a heuristic of
+/// this crate: it pairs the ratios WCAG 2 requires with the Lc values the APCA
+/// guidelines suggest for the same kinds of text, and interpolates linearly
+/// between them.
+///
+/// # Arguments
+///
+/// * `ratio` - WCAG 2 contrast ratio, from 1 to 21.
+///
+/// # Returns
+///
+/// The absolute Lc value, from 0 to 106.
+pub fn lc_from_ratio(ratio: f64) -> f64 {
+    let (first_ratio, first_lc) = RATIO_TO_LC[0];
+    if ratio <= first_ratio {
+        return first_lc;
+    }
+    for pair in RATIO_TO_LC.windows(2) {
+        let (start_ratio, start_lc) = pair[0];
+        let (end_ratio, end_lc) = pair[1];
+        if ratio <= end_ratio {
+            let amount = (ratio - start_ratio) / (end_ratio - start_ratio);
+            return lerp(start_lc, end_lc, amount);
+        }
+    }
+    RATIO_TO_LC[RATIO_TO_LC.len() - 1].1
+}
+
+/// Returns the lowest tone >= `tone` whose APCA contrast, as text on
+/// background `tone`, reaches `lc`.
+///
+/// # Arguments
+///
+/// * `tone` - Tone of the background.
+/// * `lc` - Desired absolute Lc.
+///
+/// # Returns
+///
+/// A tone from 0 to 100, or `None` if `lc` cannot be achieved with `tone`.
+pub fn apca_lighter(tone: f64, lc: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    let reaches = |text_tone: f64| apca_contrast_of_tones(text_tone, tone).abs() >= lc;
+    if !reaches(100.0) {
+        return None;
+    }
+
+    let mut low = tone;
+    let mut high = 100.0;
+    for _ in 0..SOLVER_ITERATIONS {
+        let middle = (low + high) / 2.0;
+        if reaches(middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    Some(high)
+}
+
+/// Tone >= `tone` that reaches `lc`. 100 if `lc` cannot be achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but, the in bounds return value may not reach the desired Lc.
+///
+/// # Arguments
+///
+/// * `tone` - Tone of the background.
+/// * `lc` - Desired absolute Lc.
+///
+/// # Returns
+///
+/// A tone from 0 to 100.
+pub fn apca_lighter_unsafe(tone: f64, lc: f64) -> f64 {
+    apca_lighter(tone, lc).unwrap_or(100.0)
+}
+
+/// Returns the highest tone <= `tone` whose APCA contrast, as text on
+/// background `tone`, reaches `lc`.
+///
+/// # Arguments
+///
+/// * `tone` - Tone of the background.
+/// * `lc` - Desired absolute Lc.
+///
+/// # Returns
+///
+/// A tone from 0 to 100, or `None` if `lc` cannot be achieved with `tone`.
+pub fn apca_darker(tone: f64, lc: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    let reaches = |text_tone: f64| apca_contrast_of_tones(text_tone, tone).abs() >= lc;
+    if !reaches(0.0) {
+        return None;
+    }
+
+    let mut low = 0.0;
+    let mut high = tone;
+    for _ in 0..SOLVER_ITERATIONS {
+        let middle = (low + high) / 2.0;
+        if reaches(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Some(low)
+}
+
+/// Tone <= `tone` that reaches `lc`. 0 if `lc` cannot be achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but, the in bounds return value may not reach the desired Lc.
+///
+/// # Arguments
+///
+/// * `tone` - Tone of the background.
+/// * `lc` - Desired absolute Lc.
+///
+/// # Returns
+///
+/// A tone from 0 to 100.
+pub fn apca_darker_unsafe(tone: f64, lc: f64) -> f64 {
+    apca_darker(tone, lc).unwrap_or(0.0)
+}
+
+/// Finds the tone of a palette, closest to `tone`, whose color reaches an APCA
+/// contrast of `lc` as text on `background_argb`.
+///
+/// Contrast is measured on the palette's actual colors, so the result holds
+/// for colors with chroma, not just for grays.
+///
+/// # Arguments
+///
+/// * `palette` - The palette to pick a tone from.
+/// * `background_argb` - ARGB representation of the background color.
+/// * `lc` - Desired absolute Lc.
+/// * `tone` - The preferred tone, from 0 to 100.
+///
+/// # Returns
+///
+/// A tone from 0 to 100, or `None` if no tone of the palette reaches `lc`.
+pub fn apca_tone_in_palette(
+    palette: &TonalPalette,
+    background_argb: u32,
+    lc: f64,
+    tone: u32,
+) -> Option<u32> {
+    (0..=100)
+        .filter(|&candidate| apca_contrast(palette.tone(candidate), background_argb).abs() >= lc)
+        .min_by_key(|&candidate| candidate.abs_diff(tone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    /// Reference values from the APCA 0.0.98G-4g implementation.
+    #[test]
+    fn contrast_matches_reference() {
+        assert_close(apca_contrast(0xff000000, 0xffffffff), 106.04067321268862);
+        assert_close(apca_contrast(0xffffffff, 0xff000000), -107.88473318309848);
+        assert_close(apca_contrast(0xff888888, 0xffffffff), 63.056469930209424);
+        assert_close(apca_contrast(0xffffffff, 0xff888888), -68.54146436644962);
+        assert_close(apca_contrast(0xff000000, 0xffaaaaaa), 58.146262578561334);
+        assert_close(apca_contrast(0xffaaaaaa, 0xff000000), -56.24113336839742);
+    }
+
+    #[test]
+    fn lc_from_ratio_interpolates_between_use_cases() {
+        assert_eq!(lc_from_ratio(1.0), 0.0);
+        assert_eq!(lc_from_ratio(4.5), 60.0);
+        assert_eq!(lc_from_ratio(7.0), 75.0);
+        assert_eq!(lc_from_ratio(5.75), 67.5);
+        assert_eq!(lc_from_ratio(21.0), 106.0);
+        assert_eq!(lc_from_ratio(0.5), 0.0);
+        assert_eq!(lc_from_ratio(30.0), 106.0);
+    }
+
+    #[test]
+    fn tone_in_palette_is_the_nearest_that_reaches_lc() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        let reaches = |tone: u32, background_argb: u32, lc: f64| {
+            apca_contrast(palette.tone(tone), background_argb).abs() >= lc
+        };
+        for background_argb in [0xffffffff, 0xff000000, palette.tone(90), palette.tone(10)] {
+            for lc in [30.0, 60.0, 75.0] {
+                for tone in [0, 40, 50, 80, 100] {
+                    let found = apca_tone_in_palette(&palette, background_argb, lc, tone).unwrap();
+                    assert!(reaches(found, background_argb, lc));
+                    let distance = found.abs_diff(tone);
+                    for closer in 0..=100u32 {
+                        if closer.abs_diff(tone) < distance {
+                            assert!(
+                                !reaches(closer, background_argb, lc),
+                                "{closer} is closer to {tone}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tone_in_palette_keeps_a_tone_that_reaches_lc() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        assert_eq!(
+            apca_tone_in_palette(&palette, 0xffffffff, 60.0, 10),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn tone_in_palette_is_none_when_lc_is_unreachable() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        assert_eq!(apca_tone_in_palette(&palette, 0xff808080, 90.0, 50), None);
+        assert_eq!(apca_tone_in_palette(&palette, 0xffffffff, 110.0, 0), None);
+    }
+
+    #[test]
+    fn contrast_of_close_colors_is_zero() {
+        assert_eq!(apca_contrast(0xff777777, 0xff777777), 0.0);
+        assert_eq!(apca_contrast(0xff777777, 0xff787878), 0.0);
+    }
+
+    #[test]
+    fn lighter_and_darker_reach_lc() {
+        for tone in [0.0, 20.0, 50.0, 80.0, 100.0] {
+            for lc in [15.0, 30.0, 45.0, 60.0, 75.0, 90.0] {
+                if let Some(lighter) = apca_lighter(tone, lc) {
+                    assert!(lighter >= tone);
+                    assert!(apca_contrast_of_tones(lighter, tone).abs() >= lc);
+                }
+                if let Some(darker) = apca_darker(tone, lc) {
+                    assert!(darker <= tone);
+                    assert!(apca_contrast_of_tones(darker, tone).abs() >= lc);
+                }
+            }
+        }
+        assert_eq!(apca_lighter(100.0, 15.0), None);
+        assert_eq!(apca_darker(0.0, 15.0), None);
+    }
+}
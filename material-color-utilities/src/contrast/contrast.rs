@@ -0,0 +1,290 @@
+use crate::utils::color_utils::{lstar_from_y, xyz_from_argb, y_from_lstar};
+
+/// The minimum contrast ratio of two colors. Contrast ratio equation =
+/// lighter + 5 / darker + 5, if lighter == darker, ratio == 1.
+pub const RATIO_MIN: f64 = 1.0;
+
+/// The maximum contrast ratio of two colors. Contrast ratio equation =
+/// lighter + 5 / darker + 5. Lighter and darker scale from 0 to 100. If
+/// lighter == 100, darker = 0, ratio == 21.
+pub const RATIO_MAX: f64 = 21.0;
+
+/// The minimum contrast ratio WCAG requires for large text and graphical
+/// objects.
+pub const RATIO_30: f64 = 3.0;
+
+/// The minimum contrast ratio WCAG AA requires for body text.
+pub const RATIO_45: f64 = 4.5;
+
+/// The minimum contrast ratio WCAG AAA requires for body text.
+pub const RATIO_70: f64 = 7.0;
+
+/// Given a color and a contrast ratio to reach, the luminance of a color that
+/// reaches that ratio with the color can be calculated. However, that
+/// luminance may not contrast as desired, i.e. the contrast ratio of the input
+/// color and the returned luminance may not reach the contrast ratio asked
+/// for.
+///
+/// When the desired contrast ratio and the result contrast ratio differ by
+/// more than this amount, an error value should be returned, or the method
+/// should be documented as 'unsafe', meaning, it will return a valid luminance
+/// but that luminance may not meet the requested contrast ratio.
+///
+/// 0.04 selected because it ensures the resulting ratio rounds to the same
+/// tenth.
+const CONTRAST_RATIO_EPSILON: f64 = 0.04;
+
+/// Color spaces that measure luminance, such as Y in XYZ, L* in L*a*b*, or T
+/// in HCT, are known as perceptually accurate color spaces.
+///
+/// To be displayed, they must gamut map to a "display space", one that has a
+/// defined limit on the number of colors. Display spaces include sRGB, more
+/// commonly understood as RGB/HSL/HSV/HSB. Gamut mapping is undefined and not
+/// defined by the color space. Any gamut mapping algorithm must choose how to
+/// sacrifice accuracy in hue, saturation, and/or lightness.
+///
+/// A principled solution is to maintain lightness, thus maintaining contrast
+/// ratio, and sacrifice hue/chroma. Sacrificing hue/chroma minimizes
+/// perceptual error, as hue is less important than lightness in the CAM16
+/// color appearance model.
+///
+/// That said, gamut mapping still introduces a luminance error. This value is
+/// the amount of luminance error to tolerate, and should be added to the
+/// returned luminance of a contrast ratio lightness solver.
+const LUMINANCE_GAMUT_MAP_TOLERANCE: f64 = 0.4;
+
+/// Contrast ratio is a measure of legibility, its used to compare the
+/// lightness of two colors. This method is used commonly in industry due to
+/// its use by WCAG.
+///
+/// To compare lightness, the colors are expressed in the XYZ color space,
+/// where Y is lightness, also known as relative luminance.
+///
+/// The equation is ratio = lighter Y + 5 / darker Y + 5.
+fn ratio_of_ys(y1: f64, y2: f64) -> f64 {
+    let lighter = y1.max(y2);
+    let darker = if lighter == y2 { y1 } else { y2 };
+    (lighter + 5.0) / (darker + 5.0)
+}
+
+/// Contrast ratio of two tones. T in HCT, L* in L*a*b*. Also known as
+/// luminance or perceptual luminance.
+///
+/// Contrast ratio is defined using Y in XYZ, relative luminance. However, Y is
+/// linear to photons, not to perception. L* is linear to perception, and
+/// converting between the two is a simple function. This method converts the
+/// tones to Y and calls `ratio_of_ys`.
+///
+/// # Arguments
+///
+/// * `t1` - A tone, T in HCT, from 0 to 100. Clamped to that range.
+/// * `t2` - A tone, T in HCT, from 0 to 100. Clamped to that range.
+///
+/// # Returns
+///
+/// The contrast ratio of the two tones, from 1 to 21.
+pub fn ratio_of_tones(t1: f64, t2: f64) -> f64 {
+    let t1 = t1.clamp(0.0, 100.0);
+    let t2 = t2.clamp(0.0, 100.0);
+    ratio_of_ys(y_from_lstar(t1), y_from_lstar(t2))
+}
+
+/// Contrast ratio of two ARGB colors.
+///
+/// The colors are compared by their relative luminance, Y in XYZ. Alpha is
+/// ignored.
+///
+/// # Arguments
+///
+/// * `argb1` - ARGB representation of a color.
+/// * `argb2` - ARGB representation of a color.
+///
+/// # Returns
+///
+/// The contrast ratio of the two colors, from 1 to 21.
+pub fn ratio_of_argb(argb1: u32, argb2: u32) -> f64 {
+    ratio_of_ys(xyz_from_argb(argb1)[1], xyz_from_argb(argb2)[1])
+}
+
+/// Returns a tone >= `tone` that ensures `ratio`.
+///
+/// # Arguments
+///
+/// * `tone` - Tone return value must contrast with.
+/// * `ratio` - Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// A tone from 0 to 100, or `None` if `ratio` cannot be achieved with `tone`.
+pub fn lighter(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    // Invert the contrast ratio equation to determine lighter Y given a ratio
+    // and darker Y.
+    let dark_y = y_from_lstar(tone);
+    let light_y = ratio * (dark_y + 5.0) - 5.0;
+    if !(0.0..=100.0).contains(&light_y) {
+        return None;
+    }
+    let real_contrast = ratio_of_ys(light_y, dark_y);
+    let delta = (real_contrast - ratio).abs();
+    if real_contrast < ratio && delta > CONTRAST_RATIO_EPSILON {
+        return None;
+    }
+
+    let return_value = lstar_from_y(light_y) + LUMINANCE_GAMUT_MAP_TOLERANCE;
+    if !(0.0..=100.0).contains(&return_value) {
+        return None;
+    }
+    Some(return_value)
+}
+
+/// Tone >= `tone` that ensures `ratio`. 100 if ratio cannot be achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but, the in bounds return value may not reach the desired ratio.
+///
+/// # Arguments
+///
+/// * `tone` - Tone return value must contrast with.
+/// * `ratio` - Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// A tone from 0 to 100.
+pub fn lighter_unsafe(tone: f64, ratio: f64) -> f64 {
+    lighter(tone, ratio).unwrap_or(100.0)
+}
+
+/// Returns a tone <= `tone` that ensures `ratio`.
+///
+/// # Arguments
+///
+/// * `tone` - Tone return value must contrast with.
+/// * `ratio` - Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// A tone from 0 to 100, or `None` if `ratio` cannot be achieved with `tone`.
+pub fn darker(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    // Invert the contrast ratio equation to determine darker Y given a ratio
+    // and lighter Y.
+    let light_y = y_from_lstar(tone);
+    let dark_y = (light_y + 5.0) / ratio - 5.0;
+    if !(0.0..=100.0).contains(&dark_y) {
+        return None;
+    }
+    let real_contrast = ratio_of_ys(light_y, dark_y);
+    let delta = (real_contrast - ratio).abs();
+    if real_contrast < ratio && delta > CONTRAST_RATIO_EPSILON {
+        return None;
+    }
+
+    // For information on 0.4 constant, see comment in lighter(tone, ratio).
+    let return_value = lstar_from_y(dark_y) - LUMINANCE_GAMUT_MAP_TOLERANCE;
+    if !(0.0..=100.0).contains(&return_value) {
+        return None;
+    }
+    Some(return_value)
+}
+
+/// Tone <= `tone` that ensures `ratio`. 0 if ratio cannot be achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but, the in bounds return value may not reach the desired ratio.
+///
+/// # Arguments
+///
+/// * `tone` - Tone return value must contrast with.
+/// * `ratio` - Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// A tone from 0 to 100.
+pub fn darker_unsafe(tone: f64, ratio: f64) -> f64 {
+    darker(tone, ratio).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn ratio_of_tones_spans_one_to_twenty_one() {
+        assert_near(ratio_of_tones(100.0, 0.0), RATIO_MAX);
+        assert_near(ratio_of_tones(0.0, 100.0), RATIO_MAX);
+        assert_near(ratio_of_tones(50.0, 50.0), RATIO_MIN);
+    }
+
+    #[test]
+    fn ratio_of_tones_clamps_out_of_bounds_input() {
+        assert_near(ratio_of_tones(-10.0, 110.0), RATIO_MAX);
+    }
+
+    #[test]
+    fn ratio_of_argb_compares_luminance() {
+        assert_near(ratio_of_argb(0xff000000, 0xffffffff), RATIO_MAX);
+        assert_near(ratio_of_argb(0xffffffff, 0xff0000ff), 8.592);
+        assert_near(ratio_of_argb(0xff777777, 0xff777777), RATIO_MIN);
+        // Alpha is ignored.
+        assert_near(ratio_of_argb(0x00000000, 0xffffffff), RATIO_MAX);
+    }
+
+    #[test]
+    fn lighter_and_darker_reach_ratio() {
+        let lighter = lighter(0.0, RATIO_45).unwrap();
+        assert_near(lighter, 49.284);
+        assert!(ratio_of_tones(0.0, lighter) >= RATIO_45);
+
+        let darker = darker(100.0, RATIO_45).unwrap();
+        assert_near(darker, 49.498);
+        assert!(ratio_of_tones(100.0, darker) >= RATIO_45);
+    }
+
+    #[test]
+    fn lighter_impossible_ratio_errors() {
+        assert_eq!(lighter(90.0, 10.0), None);
+    }
+
+    #[test]
+    fn lighter_out_of_bounds_input_errors() {
+        assert_eq!(lighter(110.0, 2.0), None);
+        assert_eq!(lighter(-10.0, 2.0), None);
+    }
+
+    #[test]
+    fn lighter_unsafe_returns_max_tone() {
+        assert_eq!(lighter_unsafe(100.0, 2.0), 100.0);
+        assert_eq!(lighter_unsafe(90.0, 10.0), 100.0);
+    }
+
+    #[test]
+    fn darker_impossible_ratio_errors() {
+        assert_eq!(darker(10.0, 20.0), None);
+    }
+
+    #[test]
+    fn darker_out_of_bounds_input_errors() {
+        assert_eq!(darker(110.0, 2.0), None);
+        assert_eq!(darker(-10.0, 2.0), None);
+    }
+
+    #[test]
+    fn darker_unsafe_returns_min_tone() {
+        assert_eq!(darker_unsafe(0.0, 2.0), 0.0);
+        assert_eq!(darker_unsafe(10.0, 20.0), 0.0);
+    }
+}
@@ -0,0 +1,78 @@
+use super::{
+    apca::{
+        apca_contrast_of_tones, apca_darker, apca_darker_unsafe, apca_lighter, apca_lighter_unsafe,
+        lc_from_ratio,
+    },
+    contrast::{darker, darker_unsafe, lighter, lighter_unsafe, ratio_of_tones},
+};
+
+/// The measure of contrast that generated schemes must satisfy.
+///
+/// Contrast requirements are always specified as WCAG 2 contrast ratios.
+/// When targeting APCA, each ratio is replaced with the APCA Lc that serves
+/// the same purpose, see `lc_from_ratio`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ContrastAlgorithm {
+    /// WCAG 2 contrast ratios, from 1 to 21.
+    #[default]
+    Wcag,
+
+    /// APCA lightness contrast (Lc), from the WCAG 3 working draft.
+    Apca,
+}
+
+impl ContrastAlgorithm {
+    /// Converts a WCAG 2 contrast ratio into a target in this algorithm's
+    /// units.
+    pub(crate) fn target(self, ratio: f64) -> f64 {
+        match self {
+            ContrastAlgorithm::Wcag => ratio,
+            ContrastAlgorithm::Apca => lc_from_ratio(ratio),
+        }
+    }
+
+    /// Contrast of a foreground tone on a background tone, in this
+    /// algorithm's units. Larger is always more contrast.
+    pub(crate) fn contrast_of_tones(self, tone: f64, background_tone: f64) -> f64 {
+        match self {
+            ContrastAlgorithm::Wcag => ratio_of_tones(tone, background_tone),
+            ContrastAlgorithm::Apca => apca_contrast_of_tones(tone, background_tone).abs(),
+        }
+    }
+
+    /// Difference in contrast small enough to be imperceptible.
+    pub(crate) fn negligible_difference(self) -> f64 {
+        match self {
+            ContrastAlgorithm::Wcag => 0.1,
+            ContrastAlgorithm::Apca => 1.0,
+        }
+    }
+
+    pub(crate) fn lighter(self, tone: f64, target: f64) -> Option<f64> {
+        match self {
+            ContrastAlgorithm::Wcag => lighter(tone, target),
+            ContrastAlgorithm::Apca => apca_lighter(tone, target),
+        }
+    }
+
+    pub(crate) fn lighter_unsafe(self, tone: f64, target: f64) -> f64 {
+        match self {
+            ContrastAlgorithm::Wcag => lighter_unsafe(tone, target),
+            ContrastAlgorithm::Apca => apca_lighter_unsafe(tone, target),
+        }
+    }
+
+    pub(crate) fn darker(self, tone: f64, target: f64) -> Option<f64> {
+        match self {
+            ContrastAlgorithm::Wcag => darker(tone, target),
+            ContrastAlgorithm::Apca => apca_darker(tone, target),
+        }
+    }
+
+    pub(crate) fn darker_unsafe(self, tone: f64, target: f64) -> f64 {
+        match self {
+            ContrastAlgorithm::Wcag => darker_unsafe(tone, target),
+            ContrastAlgorithm::Apca => apca_darker_unsafe(tone, target),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod apca;
+mod contrast;
+mod contrast_algorithm;
+
+pub use apca::*;
+pub use contrast::*;
+pub use contrast_algorithm::*;
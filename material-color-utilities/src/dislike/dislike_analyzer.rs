@@ -0,0 +1,96 @@
+use crate::{hct::Hct, palettes::TonalPalette};
+
+/// Check and/or fix universally disliked colors.
+///
+/// Color science studies of color preference indicate universal distaste for
+/// dark yellow-greens, and also show this is correlated to distate for
+/// biological waste and rotting food.
+///
+/// See Palmer and Schloss, 2010 or Schloss and Palmer's Chapter 21 in Handbook
+/// of Color Psychology (2015).
+pub struct DislikeAnalyzer;
+
+impl DislikeAnalyzer {
+    /// Returns true if a color is disliked.
+    ///
+    /// # Arguments
+    ///
+    /// * `hct` - A color to be judged.
+    ///
+    /// # Returns
+    ///
+    /// Whether the color is disliked.
+    ///
+    /// Disliked is defined as a dark yellow-green that is not neutral.
+    pub fn is_disliked(hct: &Hct) -> bool {
+        let hue_passes = hct.hue().round() >= 90.0 && hct.hue().round() <= 111.0;
+        let chroma_passes = hct.chroma().round() > 16.0;
+        let tone_passes = hct.tone().round() < 65.0;
+
+        hue_passes && chroma_passes && tone_passes
+    }
+
+    /// If a color is disliked, lighten it to make it likable.
+    ///
+    /// # Arguments
+    ///
+    /// * `hct` - A color to be judged.
+    ///
+    /// # Returns
+    ///
+    /// A new color if the original color is disliked, or the original color if
+    /// it is acceptable.
+    pub fn fix_if_disliked(hct: Hct) -> Hct {
+        if DislikeAnalyzer::is_disliked(&hct) {
+            return Hct::from_hct(hct.hue(), hct.chroma(), 70.0);
+        }
+
+        hct
+    }
+
+    /// If a tone of a palette is disliked, move it to the nearest tone that
+    /// is not.
+    ///
+    /// Unlike [`DislikeAnalyzer::fix_if_disliked`], which always lifts to tone
+    /// 70, this keeps the color as close as possible to its intended lightness.
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - The palette the tone is taken from.
+    /// * `tone` - The proposed tone.
+    /// * `prefer_darker` - Which way to move when a darker and a lighter tone
+    ///   are equally near.
+    /// * `is_allowed` - Further requirements on the new tone, such as contrast
+    ///   against a background.
+    ///
+    /// # Returns
+    ///
+    /// The nearest allowed tone whose color is not disliked, or `tone` if it
+    /// is not disliked or no tone of the palette qualifies.
+    pub fn nearest_liked_tone(
+        palette: &TonalPalette,
+        tone: f64,
+        prefer_darker: bool,
+        is_allowed: impl Fn(f64) -> bool,
+    ) -> f64 {
+        let is_liked = |tone: f64| !DislikeAnalyzer::is_disliked(&palette.get_hct(tone));
+        if is_liked(tone) {
+            return tone;
+        }
+
+        let step = if prefer_darker { -1.0 } else { 1.0 };
+        for distance in 1..=100 {
+            let distance = distance as f64;
+            for candidate in [tone + step * distance, tone - step * distance] {
+                if (0.0..=100.0).contains(&candidate)
+                    && is_allowed(candidate)
+                    && is_liked(candidate)
+                {
+                    return candidate;
+                }
+            }
+        }
+
+        tone
+    }
+}
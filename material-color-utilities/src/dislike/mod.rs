@@ -0,0 +1,3 @@
+mod dislike_analyzer;
+
+pub use dislike_analyzer::*;
@@ -0,0 +1,528 @@
+use crate::{
+    contrast::{apca_contrast, ContrastAlgorithm, SOLVER_ITERATIONS},
+    dislike::DislikeAnalyzer,
+    hct::Hct,
+    palettes::TonalPalette,
+    scheme::DynamicScheme,
+    utils::math_utils::clamp_double,
+};
+
+use super::{
+    contrast_curve::ContrastCurve,
+    tone_delta_pair::{ToneDeltaPair, TonePolarity},
+};
+
+/// A color that adjusts itself based on UI state, represented by
+/// `DynamicScheme`.
+///
+/// This color automatically adjusts to accommodate a desired contrast level,
+/// or other adjustments such as differing in light mode versus dark mode, or
+/// what the theme is, or what the color that produced the theme is, etc.
+///
+/// Colors without backgrounds do not change tone when contrast changes. Colors
+/// with backgrounds become closer to their background as contrast lowers, and
+/// further when contrast increases.
+///
+/// Prefer the static constructors. They provide a much more simple interface,
+/// such as requiring just a hexcode, or just a hexcode and a background.
+///
+/// Ultimately, each component necessary for calculating a color, adjusting it
+/// for a desired contrast level, and ensuring it has a certain lightness/tone
+/// difference from another color, is provided by a function that takes a
+/// `DynamicScheme` and returns a value. This ensures ultimate flexibility, any
+/// desired behavior of a color for any design system, but it usually
+/// unnecessary. See the default constructor for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct DynamicColor {
+    /// The name of the dynamic color.
+    pub name: &'static str,
+
+    /// Function that provides a `TonalPalette` given a `DynamicScheme`. A
+    /// `TonalPalette` is defined by a hue and chroma, so this replaces the
+    /// need to specify hue/chroma. By providing a tonal palette, when contrast
+    /// adjustments are made, intended chroma can be preserved.
+    pub palette: fn(&DynamicScheme) -> &TonalPalette,
+
+    /// Function that provides a tone, given a `DynamicScheme`.
+    pub tone: fn(&DynamicScheme) -> f64,
+
+    /// Whether this dynamic color is a background, with some other color as
+    /// the foreground.
+    pub is_background: bool,
+
+    /// The background of the dynamic color (as a function of a
+    /// `DynamicScheme`), if it exists.
+    pub background: Option<fn(&DynamicScheme) -> DynamicColor>,
+
+    /// A second background of the dynamic color (as a function of a
+    /// `DynamicScheme`), if it exists.
+    pub second_background: Option<fn(&DynamicScheme) -> DynamicColor>,
+
+    /// A `ContrastCurve` object specifying how its contrast against its
+    /// background should behave in various contrast levels options.
+    pub contrast_curve: Option<ContrastCurve>,
+
+    /// A `ToneDeltaPair` object specifying a tone delta constraint between two
+    /// colors. One of them must be the color being constructed.
+    pub tone_delta_pair: Option<fn(&DynamicScheme) -> ToneDeltaPair>,
+
+    /// Whether a tone that lands on a disliked color is moved to the nearest
+    /// tone that is not, as long as contrast against the background and the
+    /// `ToneDeltaPair` constraint still hold. See `DislikeAnalyzer`. `false`
+    /// unless set after construction.
+    pub avoid_disliked: bool,
+}
+
+impl DynamicColor {
+    /// The base constructor for `DynamicColor`.
+    ///
+    /// _Strongly_ prefer using one of the convenience constructors. This
+    /// constructor is only provided for completeness, and for the catalogue of
+    /// roles in `MaterialDynamicColors`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dynamic color.
+    /// * `palette` - Function that provides a `TonalPalette` given a
+    ///   `DynamicScheme`.
+    /// * `tone` - Function that provides a tone, given a `DynamicScheme`.
+    /// * `is_background` - Whether this dynamic color is a background, with
+    ///   some other color as the foreground.
+    /// * `background` - The background of the dynamic color, if it exists.
+    /// * `second_background` - A second background of the dynamic color, if it
+    ///   exists.
+    /// * `contrast_curve` - How its contrast against its background should
+    ///   behave in various contrast levels options.
+    /// * `tone_delta_pair` - A tone delta constraint between two colors. One of
+    ///   them must be the color being constructed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: &'static str,
+        palette: fn(&DynamicScheme) -> &TonalPalette,
+        tone: fn(&DynamicScheme) -> f64,
+        is_background: bool,
+        background: Option<fn(&DynamicScheme) -> DynamicColor>,
+        second_background: Option<fn(&DynamicScheme) -> DynamicColor>,
+        contrast_curve: Option<ContrastCurve>,
+        tone_delta_pair: Option<fn(&DynamicScheme) -> ToneDeltaPair>,
+    ) -> DynamicColor {
+        DynamicColor {
+            name,
+            palette,
+            tone,
+            is_background,
+            background,
+            second_background,
+            contrast_curve,
+            tone_delta_pair,
+            avoid_disliked: false,
+        }
+    }
+
+    /// A convenience constructor for `DynamicColor`. Creates a color that is
+    /// not a background and has no contrast requirements.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dynamic color.
+    /// * `palette` - Function that provides a `TonalPalette` given a
+    ///   `DynamicScheme`.
+    /// * `tone` - Function that provides a tone, given a `DynamicScheme`.
+    pub fn from_palette(
+        name: &'static str,
+        palette: fn(&DynamicScheme) -> &TonalPalette,
+        tone: fn(&DynamicScheme) -> f64,
+    ) -> DynamicColor {
+        DynamicColor::new(name, palette, tone, false, None, None, None, None)
+    }
+
+    /// Returns an ARGB integer (i.e. a hex code).
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - Defines the conditions of the user interface, for example,
+    ///   whether or not it is dark mode or light mode, and what the desired
+    ///   contrast level is.
+    pub fn get_argb(&self, scheme: &DynamicScheme) -> u32 {
+        self.get_hct(scheme).to_int()
+    }
+
+    /// Returns a color, expressed in the HCT color space, that this
+    /// `DynamicColor` is under the conditions in `scheme`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - Defines the conditions of the user interface, for example,
+    ///   whether or not it is dark mode or light mode, and what the desired
+    ///   contrast level is.
+    pub fn get_hct(&self, scheme: &DynamicScheme) -> Hct {
+        let tone = self.get_tone(scheme);
+        (self.palette)(scheme).get_hct(tone)
+    }
+
+    /// Returns the tone in HCT, ranging from 0 to 100, of the resolved color
+    /// given scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - Defines the conditions of the user interface, for example,
+    ///   whether or not it is dark mode or light mode, and what the desired
+    ///   contrast level is.
+    pub fn get_tone(&self, scheme: &DynamicScheme) -> f64 {
+        let tone = self.solve_tone(scheme);
+        match scheme.contrast_algorithm {
+            ContrastAlgorithm::Wcag => tone,
+            ContrastAlgorithm::Apca => self.apca_palette_tone(scheme, tone),
+        }
+    }
+
+    /// Solves for the tone of this color, measuring contrast between tones.
+    fn solve_tone(&self, scheme: &DynamicScheme) -> f64 {
+        let algorithm = scheme.contrast_algorithm;
+        let decreasing_contrast = scheme.contrast_level < 0.0;
+
+        // Case 1: dual foreground, pair of colors with delta constraint.
+        if let Some(tone_delta_pair) = self.tone_delta_pair {
+            let ToneDeltaPair {
+                role_a,
+                role_b,
+                delta,
+                polarity,
+                stay_together,
+            } = tone_delta_pair(scheme);
+
+            let bg_tone = self.background_tone(scheme);
+
+            let a_is_nearer = polarity == TonePolarity::Nearer
+                || (polarity == TonePolarity::Lighter && !scheme.is_dark)
+                || (polarity == TonePolarity::Darker && scheme.is_dark);
+            let (nearer, farther) = if a_is_nearer {
+                (role_a, role_b)
+            } else {
+                (role_b, role_a)
+            };
+            let am_nearer = self.name == nearer.name;
+            let expansion_dir = if scheme.is_dark { 1.0 } else { -1.0 };
+
+            // 1st round: solve to min, each
+            let n_contrast = nearer.desired_contrast(scheme);
+            let f_contrast = farther.desired_contrast(scheme);
+
+            // If a color is good enough, it is not adjusted.
+            // Initial and adjusted tones for `nearer`
+            let n_initial_tone = (nearer.tone)(scheme);
+            let mut n_tone = if algorithm.contrast_of_tones(n_initial_tone, bg_tone) >= n_contrast {
+                n_initial_tone
+            } else {
+                DynamicColor::foreground_tone_for(algorithm, bg_tone, n_contrast)
+            };
+            // Initial and adjusted tones for `farther`
+            let f_initial_tone = (farther.tone)(scheme);
+            let mut f_tone = if algorithm.contrast_of_tones(f_initial_tone, bg_tone) >= f_contrast {
+                f_initial_tone
+            } else {
+                DynamicColor::foreground_tone_for(algorithm, bg_tone, f_contrast)
+            };
+
+            if decreasing_contrast {
+                // If decreasing contrast, adjust color to the "bare minimum"
+                // that satisfies contrast.
+                n_tone = DynamicColor::foreground_tone_for(algorithm, bg_tone, n_contrast);
+                f_tone = DynamicColor::foreground_tone_for(algorithm, bg_tone, f_contrast);
+            }
+
+            // If constraint is not satisfied, try another round.
+            if (f_tone - n_tone) * expansion_dir < delta {
+                // 2nd round: expand farther to match delta.
+                f_tone = clamp_double(0.0, 100.0, n_tone + delta * expansion_dir);
+                // If constraint is not satisfied, try another round.
+                if (f_tone - n_tone) * expansion_dir < delta {
+                    // 3rd round: contract nearer to match delta.
+                    n_tone = clamp_double(0.0, 100.0, f_tone - delta * expansion_dir);
+                }
+            }
+
+            // Avoids disliked colors, without giving up contrast with the
+            // background or entering the awkward zone below. Moving `nearer`
+            // away from `farther` is preferred, as it keeps the delta.
+            if nearer.avoid_disliked {
+                n_tone = nearer.liked_tone(scheme, n_tone, bg_tone, expansion_dir > 0.0);
+                if (f_tone - n_tone) * expansion_dir < delta {
+                    f_tone = clamp_double(0.0, 100.0, n_tone + delta * expansion_dir);
+                }
+            }
+
+            // Avoids the 50-59 awkward zone.
+            if (50.0..60.0).contains(&n_tone) || ((50.0..60.0).contains(&f_tone) && stay_together) {
+                // If `nearer` is in the awkward zone, move it away, together
+                // with `farther`. When the pair must stay together, the same
+                // applies if `farther` is in the awkward zone.
+                if expansion_dir > 0.0 {
+                    n_tone = 60.0;
+                    f_tone = f_tone.max(n_tone + delta * expansion_dir);
+                } else {
+                    n_tone = 49.0;
+                    f_tone = f_tone.min(n_tone + delta * expansion_dir);
+                }
+            } else if (50.0..60.0).contains(&f_tone) {
+                // Not required to add the delta to nearer, in this case.
+                f_tone = if expansion_dir > 0.0 { 60.0 } else { 49.0 };
+            }
+
+            if farther.avoid_disliked {
+                let liked_tone = farther.liked_tone(scheme, f_tone, bg_tone, expansion_dir < 0.0);
+                if (liked_tone - n_tone) * expansion_dir >= delta {
+                    f_tone = liked_tone;
+                }
+            }
+
+            // Returns `n_tone` if this color is `nearer`, otherwise `f_tone`.
+            return if am_nearer { n_tone } else { f_tone };
+        }
+
+        // Case 2: No contrast pair; just solve for itself.
+        let mut answer = (self.tone)(scheme);
+
+        let background = match self.background {
+            Some(background) => background,
+            // No adjustment for colors with no background.
+            None => return answer,
+        };
+
+        let bg_tone = background(scheme).get_tone(scheme);
+
+        let desired_contrast = self.desired_contrast(scheme);
+
+        if algorithm.contrast_of_tones(answer, bg_tone) < desired_contrast {
+            // Rough improvement.
+            answer = DynamicColor::foreground_tone_for(algorithm, bg_tone, desired_contrast);
+        }
+
+        if decreasing_contrast {
+            answer = DynamicColor::foreground_tone_for(algorithm, bg_tone, desired_contrast);
+        }
+
+        if self.is_background && (50.0..60.0).contains(&answer) {
+            // Must adjust
+            answer = if algorithm.contrast_of_tones(49.0, bg_tone) >= desired_contrast {
+                49.0
+            } else {
+                60.0
+            };
+        }
+
+        if self.avoid_disliked {
+            answer = self.liked_tone(scheme, answer, bg_tone, bg_tone > answer);
+        }
+
+        if let Some(second_background) = self.second_background {
+            // Case 3: Adjust for dual backgrounds.
+            let bg_tone1 = bg_tone;
+            let bg_tone2 = second_background(scheme).get_tone(scheme);
+
+            let upper = bg_tone1.max(bg_tone2);
+            let lower = bg_tone1.min(bg_tone2);
+
+            if algorithm.contrast_of_tones(answer, upper) >= desired_contrast
+                && algorithm.contrast_of_tones(answer, lower) >= desired_contrast
+            {
+                return answer;
+            }
+
+            // The darkest light tone that satisfies the desired contrast, if
+            // such contrast can be reached.
+            let light_option = algorithm.lighter(upper, desired_contrast);
+
+            // The lightest dark tone that satisfies the desired contrast, if
+            // such contrast can be reached.
+            let dark_option = algorithm.darker(lower, desired_contrast);
+
+            let prefers_light = DynamicColor::tone_prefers_light_foreground(bg_tone1)
+                || DynamicColor::tone_prefers_light_foreground(bg_tone2);
+            if prefers_light {
+                return light_option.unwrap_or(100.0);
+            }
+            return match (light_option, dark_option) {
+                (Some(light), None) => light,
+                (_, dark) => dark.unwrap_or(0.0),
+            };
+        }
+
+        answer
+    }
+
+    /// APCA does not measure luminance the way tone does, so a color with
+    /// chroma can fall short of an Lc that a gray of the same tone reaches.
+    /// Moves `tone` away from the background, as little as possible, until the
+    /// palette's color reaches the desired Lc on every background. When no
+    /// tone in that direction does, the nearest tone on the other side of the
+    /// background that does is used. When no tone does, the color gets as
+    /// close as it can.
+    fn apca_palette_tone(&self, scheme: &DynamicScheme, tone: f64) -> f64 {
+        let background = match self.background {
+            Some(background) => background(scheme),
+            None => return tone,
+        };
+        let bg_tone = background.get_tone(scheme);
+        let mut bg_argbs = vec![(background.palette)(scheme).fractional_tone(bg_tone)];
+        if let Some(second_background) = self.second_background {
+            bg_argbs.push(second_background(scheme).get_argb(scheme));
+        }
+
+        let lc = self.desired_contrast(scheme);
+        let palette = (self.palette)(scheme);
+        let contrast = |tone: f64| {
+            let argb = palette.fractional_tone(tone);
+            bg_argbs
+                .iter()
+                .map(|&bg_argb| apca_contrast(argb, bg_argb).abs())
+                .fold(f64::INFINITY, f64::min)
+        };
+        let reaches = |tone: f64| contrast(tone) >= lc;
+        if reaches(tone) {
+            return tone;
+        }
+        let limit = if tone >= bg_tone { 100.0 } else { 0.0 };
+        let (mut short, mut long) = if reaches(limit) {
+            (tone, limit)
+        } else if reaches(100.0 - limit) {
+            (bg_tone, 100.0 - limit)
+        } else {
+            return [tone, limit, 100.0 - limit]
+                .into_iter()
+                .max_by(|a, b| contrast(*a).total_cmp(&contrast(*b)))
+                .unwrap();
+        };
+        for _ in 0..SOLVER_ITERATIONS {
+            let middle = (short + long) / 2.0;
+            if reaches(middle) {
+                long = middle;
+            } else {
+                short = middle;
+            }
+        }
+        long
+    }
+
+    /// Moves `tone` to the nearest tone whose color isn't disliked, without
+    /// losing contrast against `bg_tone` below what this color asks for, and
+    /// outside the 50-59 awkward zone.
+    fn liked_tone(
+        &self,
+        scheme: &DynamicScheme,
+        tone: f64,
+        bg_tone: f64,
+        prefer_darker: bool,
+    ) -> f64 {
+        let algorithm = scheme.contrast_algorithm;
+        let min_contrast = self
+            .desired_contrast(scheme)
+            .min(algorithm.contrast_of_tones(tone, bg_tone));
+        DislikeAnalyzer::nearest_liked_tone((self.palette)(scheme), tone, prefer_darker, |tone| {
+            !(50.0..60.0).contains(&tone)
+                && algorithm.contrast_of_tones(tone, bg_tone) >= min_contrast
+        })
+    }
+
+    /// Given a background tone, find a foreground tone, while ensuring they
+    /// reach a contrast ratio that is as close to `ratio` as possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `bg_tone` - Tone in HCT. Range is 0 to 100, undefined behavior when it
+    ///   falls outside that range.
+    /// * `ratio` - The contrast ratio desired between `bg_tone` and the return
+    ///   value.
+    pub fn foreground_tone(bg_tone: f64, ratio: f64) -> f64 {
+        DynamicColor::foreground_tone_for(ContrastAlgorithm::Wcag, bg_tone, ratio)
+    }
+
+    /// Adjust a tone down such that white has 4.5 contrast, if the tone is
+    /// reasonably close to supporting it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - The tone to adjust.
+    pub fn enable_light_foreground(tone: f64) -> f64 {
+        if DynamicColor::tone_prefers_light_foreground(tone)
+            && !DynamicColor::tone_allows_light_foreground(tone)
+        {
+            return 49.0;
+        }
+        tone
+    }
+
+    /// People prefer white foregrounds on ~T60-70. Observed over time, and also
+    /// by Andrew Somers during research for APCA.
+    ///
+    /// T60 used as to create the smallest discontinuity possible when skipping
+    /// down to T49 in order to ensure light foregrounds.
+    ///
+    /// Since `tertiary_container` in dark monochrome scheme requires a tone of
+    /// 60, it should not be adjusted. Therefore, 60 is excluded here.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - The tone of the background.
+    pub fn tone_prefers_light_foreground(tone: f64) -> bool {
+        tone.round() < 60.0
+    }
+
+    /// Tones less than ~T50 always permit white at 4.5 contrast.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - The tone of the background.
+    pub fn tone_allows_light_foreground(tone: f64) -> bool {
+        tone.round() <= 49.0
+    }
+
+    fn background_tone(&self, scheme: &DynamicScheme) -> f64 {
+        let background = self
+            .background
+            .expect("a color in a tone delta pair must have a background");
+        background(scheme).get_tone(scheme)
+    }
+
+    fn desired_contrast(&self, scheme: &DynamicScheme) -> f64 {
+        let ratio = self
+            .contrast_curve
+            .expect("a color with a background must have a contrast curve")
+            .get(scheme.contrast_level);
+        scheme.contrast_algorithm.target(ratio)
+    }
+
+    /// Given a background tone, find a foreground tone, while ensuring they
+    /// reach a contrast that is as close to `target` as possible, as measured
+    /// by `algorithm`.
+    fn foreground_tone_for(algorithm: ContrastAlgorithm, bg_tone: f64, target: f64) -> f64 {
+        let lighter_tone = algorithm.lighter_unsafe(bg_tone, target);
+        let darker_tone = algorithm.darker_unsafe(bg_tone, target);
+        let lighter_contrast = algorithm.contrast_of_tones(lighter_tone, bg_tone);
+        let darker_contrast = algorithm.contrast_of_tones(darker_tone, bg_tone);
+        let prefer_lighter = DynamicColor::tone_prefers_light_foreground(bg_tone);
+
+        if prefer_lighter {
+            // "Negligible difference" handles an edge case where the initial
+            // contrast ratio is high (ex. 13.0), and the ratio passed to the
+            // function is that high ratio, and both the lighter and darker
+            // ratio fails to pass that ratio.
+            let negligible_difference = (lighter_contrast - darker_contrast).abs()
+                < algorithm.negligible_difference()
+                && lighter_contrast < target
+                && darker_contrast < target;
+            if lighter_contrast >= target
+                || lighter_contrast >= darker_contrast
+                || negligible_difference
+            {
+                lighter_tone
+            } else {
+                darker_tone
+            }
+        } else if darker_contrast >= target || darker_contrast >= lighter_contrast {
+            darker_tone
+        } else {
+            lighter_tone
+        }
+    }
+}
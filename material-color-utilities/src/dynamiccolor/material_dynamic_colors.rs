@@ -0,0 +1,1200 @@
+use crate::{
+    hct::Hct,
+    scheme::{DynamicScheme, Variant},
+};
+
+use super::{
+    contrast_curve::ContrastCurve,
+    dynamic_color::DynamicColor,
+    tone_delta_pair::{ToneDeltaPair, TonePolarity},
+};
+
+/// Named colors, otherwise known as tokens, or roles, in the Material Design
+/// system.
+pub struct MaterialDynamicColors;
+
+impl MaterialDynamicColors {
+    /// The surface that other colors measure their contrast against: the
+    /// brightest surface in dark mode and the dimmest in light mode.
+    pub fn highest_surface(s: &DynamicScheme) -> DynamicColor {
+        if s.is_dark {
+            MaterialDynamicColors::surface_bright()
+        } else {
+            MaterialDynamicColors::surface_dim()
+        }
+    }
+
+    // Compatibility Keys Colors for Android
+
+    pub fn primary_palette_key_color() -> DynamicColor {
+        DynamicColor::from_palette(
+            "primary_palette_key_color",
+            |s| &s.primary_palette,
+            |s| s.primary_palette.key_color().tone(),
+        )
+    }
+
+    pub fn secondary_palette_key_color() -> DynamicColor {
+        DynamicColor::from_palette(
+            "secondary_palette_key_color",
+            |s| &s.secondary_palette,
+            |s| s.secondary_palette.key_color().tone(),
+        )
+    }
+
+    pub fn tertiary_palette_key_color() -> DynamicColor {
+        DynamicColor::from_palette(
+            "tertiary_palette_key_color",
+            |s| &s.tertiary_palette,
+            |s| s.tertiary_palette.key_color().tone(),
+        )
+    }
+
+    pub fn neutral_palette_key_color() -> DynamicColor {
+        DynamicColor::from_palette(
+            "neutral_palette_key_color",
+            |s| &s.neutral_palette,
+            |s| s.neutral_palette.key_color().tone(),
+        )
+    }
+
+    pub fn neutral_variant_palette_key_color() -> DynamicColor {
+        DynamicColor::from_palette(
+            "neutral_variant_palette_key_color",
+            |s| &s.neutral_variant_palette,
+            |s| s.neutral_variant_palette.key_color().tone(),
+        )
+    }
+
+    // Surfaces
+
+    pub fn background() -> DynamicColor {
+        DynamicColor::new(
+            "background",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 6.0 } else { 98.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn on_background() -> DynamicColor {
+        DynamicColor::new(
+            "on_background",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 90.0 } else { 10.0 },
+            false,
+            Some(|_| MaterialDynamicColors::background()),
+            None,
+            Some(ContrastCurve::new(3.0, 3.0, 4.5, 7.0)),
+            None,
+        )
+    }
+
+    pub fn surface() -> DynamicColor {
+        DynamicColor::new(
+            "surface",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 6.0 } else { 98.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_dim() -> DynamicColor {
+        DynamicColor::new(
+            "surface_dim",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 6.0 } else { 87.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_bright() -> DynamicColor {
+        DynamicColor::new(
+            "surface_bright",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 24.0 } else { 98.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_container_lowest() -> DynamicColor {
+        DynamicColor::new(
+            "surface_container_lowest",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 4.0 } else { 100.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_container_low() -> DynamicColor {
+        DynamicColor::new(
+            "surface_container_low",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 10.0 } else { 96.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_container() -> DynamicColor {
+        DynamicColor::new(
+            "surface_container",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 12.0 } else { 94.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_container_high() -> DynamicColor {
+        DynamicColor::new(
+            "surface_container_high",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 17.0 } else { 92.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_container_highest() -> DynamicColor {
+        DynamicColor::new(
+            "surface_container_highest",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 22.0 } else { 90.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn on_surface() -> DynamicColor {
+        DynamicColor::new(
+            "on_surface",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 90.0 } else { 10.0 },
+            false,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn surface_variant() -> DynamicColor {
+        DynamicColor::new(
+            "surface_variant",
+            |s| &s.neutral_variant_palette,
+            |s| if s.is_dark { 30.0 } else { 90.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn on_surface_variant() -> DynamicColor {
+        DynamicColor::new(
+            "on_surface_variant",
+            |s| &s.neutral_variant_palette,
+            |s| if s.is_dark { 80.0 } else { 30.0 },
+            false,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 11.0)),
+            None,
+        )
+    }
+
+    pub fn inverse_surface() -> DynamicColor {
+        DynamicColor::new(
+            "inverse_surface",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 90.0 } else { 20.0 },
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn inverse_on_surface() -> DynamicColor {
+        DynamicColor::new(
+            "inverse_on_surface",
+            |s| &s.neutral_palette,
+            |s| if s.is_dark { 20.0 } else { 95.0 },
+            false,
+            Some(|_| MaterialDynamicColors::inverse_surface()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn outline() -> DynamicColor {
+        DynamicColor::new(
+            "outline",
+            |s| &s.neutral_variant_palette,
+            |s| if s.is_dark { 60.0 } else { 50.0 },
+            false,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.5, 3.0, 4.5, 7.0)),
+            None,
+        )
+    }
+
+    pub fn outline_variant() -> DynamicColor {
+        DynamicColor::new(
+            "outline_variant",
+            |s| &s.neutral_variant_palette,
+            |s| if s.is_dark { 30.0 } else { 80.0 },
+            false,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            None,
+        )
+    }
+
+    pub fn shadow() -> DynamicColor {
+        DynamicColor::new(
+            "shadow",
+            |s| &s.neutral_palette,
+            |_| 0.0,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn scrim() -> DynamicColor {
+        DynamicColor::new(
+            "scrim",
+            |s| &s.neutral_palette,
+            |_| 0.0,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    pub fn surface_tint() -> DynamicColor {
+        DynamicColor::new(
+            "surface_tint",
+            |s| &s.primary_palette,
+            |s| if s.is_dark { 80.0 } else { 40.0 },
+            true,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    // Primaries
+
+    pub fn primary() -> DynamicColor {
+        DynamicColor::new(
+            "primary",
+            |s| &s.primary_palette,
+            |s| {
+                if is_monochrome(s) {
+                    if s.is_dark {
+                        100.0
+                    } else {
+                        0.0
+                    }
+                } else if s.is_dark {
+                    80.0
+                } else {
+                    40.0
+                }
+            },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 7.0)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::primary_container(),
+                    MaterialDynamicColors::primary(),
+                    10.0,
+                    TonePolarity::Nearer,
+                    false,
+                )
+            }),
+        )
+    }
+
+    pub fn on_primary() -> DynamicColor {
+        DynamicColor::new(
+            "on_primary",
+            |s| &s.primary_palette,
+            |s| {
+                if is_monochrome(s) {
+                    if s.is_dark {
+                        10.0
+                    } else {
+                        90.0
+                    }
+                } else if s.is_dark {
+                    20.0
+                } else {
+                    100.0
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::primary()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn primary_container() -> DynamicColor {
+        DynamicColor {
+            avoid_disliked: true,
+            ..DynamicColor::new(
+                "primary_container",
+                |s| &s.primary_palette,
+                |s| {
+                    if is_fidelity(s) {
+                        s.source_color_hct.tone()
+                    } else if is_monochrome(s) {
+                        if s.is_dark {
+                            85.0
+                        } else {
+                            25.0
+                        }
+                    } else if s.is_dark {
+                        30.0
+                    } else {
+                        90.0
+                    }
+                },
+                true,
+                Some(MaterialDynamicColors::highest_surface),
+                None,
+                Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+                Some(|_| {
+                    ToneDeltaPair::new(
+                        MaterialDynamicColors::primary_container(),
+                        MaterialDynamicColors::primary(),
+                        10.0,
+                        TonePolarity::Nearer,
+                        false,
+                    )
+                }),
+            )
+        }
+    }
+
+    pub fn on_primary_container() -> DynamicColor {
+        DynamicColor::new(
+            "on_primary_container",
+            |s| &s.primary_palette,
+            |s| {
+                if is_fidelity(s) {
+                    DynamicColor::foreground_tone(
+                        (MaterialDynamicColors::primary_container().tone)(s),
+                        4.5,
+                    )
+                } else if is_monochrome(s) {
+                    if s.is_dark {
+                        0.0
+                    } else {
+                        100.0
+                    }
+                } else if s.is_dark {
+                    90.0
+                } else {
+                    10.0
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::primary_container()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn inverse_primary() -> DynamicColor {
+        DynamicColor::new(
+            "inverse_primary",
+            |s| &s.primary_palette,
+            |s| if s.is_dark { 40.0 } else { 80.0 },
+            false,
+            Some(|_| MaterialDynamicColors::inverse_surface()),
+            None,
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 7.0)),
+            None,
+        )
+    }
+
+    // Secondaries
+
+    pub fn secondary() -> DynamicColor {
+        DynamicColor::new(
+            "secondary",
+            |s| &s.secondary_palette,
+            |s| if s.is_dark { 80.0 } else { 40.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 7.0)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::secondary_container(),
+                    MaterialDynamicColors::secondary(),
+                    10.0,
+                    TonePolarity::Nearer,
+                    false,
+                )
+            }),
+        )
+    }
+
+    pub fn on_secondary() -> DynamicColor {
+        DynamicColor::new(
+            "on_secondary",
+            |s| &s.secondary_palette,
+            |s| {
+                if is_monochrome(s) {
+                    if s.is_dark {
+                        10.0
+                    } else {
+                        100.0
+                    }
+                } else if s.is_dark {
+                    20.0
+                } else {
+                    100.0
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::secondary()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn secondary_container() -> DynamicColor {
+        DynamicColor {
+            avoid_disliked: true,
+            ..DynamicColor::new(
+                "secondary_container",
+                |s| &s.secondary_palette,
+                |s| {
+                    let initial_tone = if s.is_dark { 30.0 } else { 90.0 };
+                    if is_monochrome(s) {
+                        if s.is_dark {
+                            30.0
+                        } else {
+                            85.0
+                        }
+                    } else if !is_fidelity(s) {
+                        initial_tone
+                    } else {
+                        find_desired_chroma_by_tone(
+                            s.secondary_palette.hue(),
+                            s.secondary_palette.chroma(),
+                            initial_tone,
+                            !s.is_dark,
+                        )
+                    }
+                },
+                true,
+                Some(MaterialDynamicColors::highest_surface),
+                None,
+                Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+                Some(|_| {
+                    ToneDeltaPair::new(
+                        MaterialDynamicColors::secondary_container(),
+                        MaterialDynamicColors::secondary(),
+                        10.0,
+                        TonePolarity::Nearer,
+                        false,
+                    )
+                }),
+            )
+        }
+    }
+
+    pub fn on_secondary_container() -> DynamicColor {
+        DynamicColor::new(
+            "on_secondary_container",
+            |s| &s.secondary_palette,
+            |s| {
+                if !is_fidelity(s) {
+                    if s.is_dark {
+                        90.0
+                    } else {
+                        10.0
+                    }
+                } else {
+                    DynamicColor::foreground_tone(
+                        (MaterialDynamicColors::secondary_container().tone)(s),
+                        4.5,
+                    )
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::secondary_container()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    // Tertiaries
+
+    pub fn tertiary() -> DynamicColor {
+        DynamicColor {
+            avoid_disliked: true,
+            ..DynamicColor::new(
+                "tertiary",
+                |s| &s.tertiary_palette,
+                |s| {
+                    if is_monochrome(s) {
+                        if s.is_dark {
+                            90.0
+                        } else {
+                            25.0
+                        }
+                    } else if s.is_dark {
+                        80.0
+                    } else {
+                        40.0
+                    }
+                },
+                true,
+                Some(MaterialDynamicColors::highest_surface),
+                None,
+                Some(ContrastCurve::new(3.0, 4.5, 7.0, 7.0)),
+                Some(|_| {
+                    ToneDeltaPair::new(
+                        MaterialDynamicColors::tertiary_container(),
+                        MaterialDynamicColors::tertiary(),
+                        10.0,
+                        TonePolarity::Nearer,
+                        false,
+                    )
+                }),
+            )
+        }
+    }
+
+    pub fn on_tertiary() -> DynamicColor {
+        DynamicColor::new(
+            "on_tertiary",
+            |s| &s.tertiary_palette,
+            |s| {
+                if is_monochrome(s) {
+                    if s.is_dark {
+                        10.0
+                    } else {
+                        90.0
+                    }
+                } else if s.is_dark {
+                    20.0
+                } else {
+                    100.0
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::tertiary()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn tertiary_container() -> DynamicColor {
+        DynamicColor {
+            avoid_disliked: true,
+            ..DynamicColor::new(
+                "tertiary_container",
+                |s| &s.tertiary_palette,
+                |s| {
+                    if is_monochrome(s) {
+                        if s.is_dark {
+                            60.0
+                        } else {
+                            49.0
+                        }
+                    } else if !is_fidelity(s) {
+                        if s.is_dark {
+                            30.0
+                        } else {
+                            90.0
+                        }
+                    } else {
+                        s.source_color_hct.tone()
+                    }
+                },
+                true,
+                Some(MaterialDynamicColors::highest_surface),
+                None,
+                Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+                Some(|_| {
+                    ToneDeltaPair::new(
+                        MaterialDynamicColors::tertiary_container(),
+                        MaterialDynamicColors::tertiary(),
+                        10.0,
+                        TonePolarity::Nearer,
+                        false,
+                    )
+                }),
+            )
+        }
+    }
+
+    pub fn on_tertiary_container() -> DynamicColor {
+        DynamicColor::new(
+            "on_tertiary_container",
+            |s| &s.tertiary_palette,
+            |s| {
+                if is_monochrome(s) {
+                    if s.is_dark {
+                        0.0
+                    } else {
+                        100.0
+                    }
+                } else if !is_fidelity(s) {
+                    if s.is_dark {
+                        90.0
+                    } else {
+                        10.0
+                    }
+                } else {
+                    DynamicColor::foreground_tone(
+                        (MaterialDynamicColors::tertiary_container().tone)(s),
+                        4.5,
+                    )
+                }
+            },
+            false,
+            Some(|_| MaterialDynamicColors::tertiary_container()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    // Errors
+
+    pub fn error() -> DynamicColor {
+        DynamicColor::new(
+            "error",
+            |s| &s.error_palette,
+            |s| if s.is_dark { 80.0 } else { 40.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 7.0)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::error_container(),
+                    MaterialDynamicColors::error(),
+                    10.0,
+                    TonePolarity::Nearer,
+                    false,
+                )
+            }),
+        )
+    }
+
+    pub fn on_error() -> DynamicColor {
+        DynamicColor::new(
+            "on_error",
+            |s| &s.error_palette,
+            |s| if s.is_dark { 20.0 } else { 100.0 },
+            false,
+            Some(|_| MaterialDynamicColors::error()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn error_container() -> DynamicColor {
+        DynamicColor::new(
+            "error_container",
+            |s| &s.error_palette,
+            |s| if s.is_dark { 30.0 } else { 90.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::error_container(),
+                    MaterialDynamicColors::error(),
+                    10.0,
+                    TonePolarity::Nearer,
+                    false,
+                )
+            }),
+        )
+    }
+
+    pub fn on_error_container() -> DynamicColor {
+        DynamicColor::new(
+            "on_error_container",
+            |s| &s.error_palette,
+            |s| if s.is_dark { 90.0 } else { 10.0 },
+            false,
+            Some(|_| MaterialDynamicColors::error_container()),
+            None,
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    // Primary Fixed Colors
+
+    pub fn primary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "primary_fixed",
+            |s| &s.primary_palette,
+            |s| if is_monochrome(s) { 40.0 } else { 90.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::primary_fixed(),
+                    MaterialDynamicColors::primary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn primary_fixed_dim() -> DynamicColor {
+        DynamicColor::new(
+            "primary_fixed_dim",
+            |s| &s.primary_palette,
+            |s| if is_monochrome(s) { 30.0 } else { 80.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::primary_fixed(),
+                    MaterialDynamicColors::primary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn on_primary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "on_primary_fixed",
+            |s| &s.primary_palette,
+            |s| if is_monochrome(s) { 100.0 } else { 10.0 },
+            false,
+            Some(|_| MaterialDynamicColors::primary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::primary_fixed()),
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn on_primary_fixed_variant() -> DynamicColor {
+        DynamicColor::new(
+            "on_primary_fixed_variant",
+            |s| &s.primary_palette,
+            |s| if is_monochrome(s) { 90.0 } else { 30.0 },
+            false,
+            Some(|_| MaterialDynamicColors::primary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::primary_fixed()),
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 11.0)),
+            None,
+        )
+    }
+
+    // Secondary Fixed Colors
+
+    pub fn secondary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "secondary_fixed",
+            |s| &s.secondary_palette,
+            |s| if is_monochrome(s) { 80.0 } else { 90.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::secondary_fixed(),
+                    MaterialDynamicColors::secondary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn secondary_fixed_dim() -> DynamicColor {
+        DynamicColor::new(
+            "secondary_fixed_dim",
+            |s| &s.secondary_palette,
+            |s| if is_monochrome(s) { 70.0 } else { 80.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::secondary_fixed(),
+                    MaterialDynamicColors::secondary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn on_secondary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "on_secondary_fixed",
+            |s| &s.secondary_palette,
+            |_| 10.0,
+            false,
+            Some(|_| MaterialDynamicColors::secondary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::secondary_fixed()),
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn on_secondary_fixed_variant() -> DynamicColor {
+        DynamicColor::new(
+            "on_secondary_fixed_variant",
+            |s| &s.secondary_palette,
+            |s| if is_monochrome(s) { 25.0 } else { 30.0 },
+            false,
+            Some(|_| MaterialDynamicColors::secondary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::secondary_fixed()),
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 11.0)),
+            None,
+        )
+    }
+
+    // Tertiary Fixed Colors
+
+    pub fn tertiary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "tertiary_fixed",
+            |s| &s.tertiary_palette,
+            |s| if is_monochrome(s) { 40.0 } else { 90.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::tertiary_fixed(),
+                    MaterialDynamicColors::tertiary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn tertiary_fixed_dim() -> DynamicColor {
+        DynamicColor::new(
+            "tertiary_fixed_dim",
+            |s| &s.tertiary_palette,
+            |s| if is_monochrome(s) { 30.0 } else { 80.0 },
+            true,
+            Some(MaterialDynamicColors::highest_surface),
+            None,
+            Some(ContrastCurve::new(1.0, 1.0, 3.0, 4.5)),
+            Some(|_| {
+                ToneDeltaPair::new(
+                    MaterialDynamicColors::tertiary_fixed(),
+                    MaterialDynamicColors::tertiary_fixed_dim(),
+                    10.0,
+                    TonePolarity::Lighter,
+                    true,
+                )
+            }),
+        )
+    }
+
+    pub fn on_tertiary_fixed() -> DynamicColor {
+        DynamicColor::new(
+            "on_tertiary_fixed",
+            |s| &s.tertiary_palette,
+            |s| if is_monochrome(s) { 100.0 } else { 10.0 },
+            false,
+            Some(|_| MaterialDynamicColors::tertiary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::tertiary_fixed()),
+            Some(ContrastCurve::new(4.5, 7.0, 11.0, 21.0)),
+            None,
+        )
+    }
+
+    pub fn on_tertiary_fixed_variant() -> DynamicColor {
+        DynamicColor::new(
+            "on_tertiary_fixed_variant",
+            |s| &s.tertiary_palette,
+            |s| if is_monochrome(s) { 90.0 } else { 30.0 },
+            false,
+            Some(|_| MaterialDynamicColors::tertiary_fixed_dim()),
+            Some(|_| MaterialDynamicColors::tertiary_fixed()),
+            Some(ContrastCurve::new(3.0, 4.5, 7.0, 11.0)),
+            None,
+        )
+    }
+}
+
+fn is_fidelity(scheme: &DynamicScheme) -> bool {
+    scheme.variant == Variant::Fidelity || scheme.variant == Variant::Content
+}
+
+fn is_monochrome(scheme: &DynamicScheme) -> bool {
+    scheme.variant == Variant::Monochrome
+}
+
+fn find_desired_chroma_by_tone(hue: f64, chroma: f64, tone: f64, by_decreasing_tone: bool) -> f64 {
+    let mut answer = tone;
+
+    let mut closest_to_chroma = Hct::from_hct(hue, chroma, tone);
+    if closest_to_chroma.chroma() < chroma {
+        let mut chroma_peak = closest_to_chroma.chroma();
+        while closest_to_chroma.chroma() < chroma {
+            answer += if by_decreasing_tone { -1.0 } else { 1.0 };
+            let potential_solution = Hct::from_hct(hue, chroma, answer);
+            if chroma_peak > potential_solution.chroma() {
+                break;
+            }
+            if (potential_solution.chroma() - chroma).abs() < 0.4 {
+                break;
+            }
+
+            chroma_peak = chroma_peak.max(potential_solution.chroma());
+            let potential_delta = (potential_solution.chroma() - chroma).abs();
+            let current_delta = (closest_to_chroma.chroma() - chroma).abs();
+            if potential_delta < current_delta {
+                closest_to_chroma = potential_solution;
+            }
+        }
+    }
+
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contrast::{apca_contrast, ratio_of_argb, ContrastAlgorithm},
+        dislike::DislikeAnalyzer,
+    };
+
+    const SEEDS: [u32; 7] = [
+        0xff4285f4, 0xffff0000, 0xffffff00, 0xff00ff00, 0xff0000ff, 0xff808080, 0xff6d5e00,
+    ];
+    const CONTRAST_LEVELS: [f64; 4] = [-1.0, 0.0, 0.5, 1.0];
+    const VARIANTS: [Variant; 9] = [
+        Variant::Monochrome,
+        Variant::Neutral,
+        Variant::TonalSpot,
+        Variant::Vibrant,
+        Variant::Expressive,
+        Variant::Fidelity,
+        Variant::Content,
+        Variant::Rainbow,
+        Variant::FruitSalad,
+    ];
+    const FOREGROUNDS: [fn() -> DynamicColor; 35] = [
+        MaterialDynamicColors::on_background,
+        MaterialDynamicColors::on_surface,
+        MaterialDynamicColors::on_surface_variant,
+        MaterialDynamicColors::inverse_on_surface,
+        MaterialDynamicColors::outline,
+        MaterialDynamicColors::outline_variant,
+        MaterialDynamicColors::primary,
+        MaterialDynamicColors::on_primary,
+        MaterialDynamicColors::primary_container,
+        MaterialDynamicColors::on_primary_container,
+        MaterialDynamicColors::inverse_primary,
+        MaterialDynamicColors::secondary,
+        MaterialDynamicColors::on_secondary,
+        MaterialDynamicColors::secondary_container,
+        MaterialDynamicColors::on_secondary_container,
+        MaterialDynamicColors::tertiary,
+        MaterialDynamicColors::on_tertiary,
+        MaterialDynamicColors::tertiary_container,
+        MaterialDynamicColors::on_tertiary_container,
+        MaterialDynamicColors::error,
+        MaterialDynamicColors::on_error,
+        MaterialDynamicColors::error_container,
+        MaterialDynamicColors::on_error_container,
+        MaterialDynamicColors::primary_fixed,
+        MaterialDynamicColors::primary_fixed_dim,
+        MaterialDynamicColors::on_primary_fixed,
+        MaterialDynamicColors::on_primary_fixed_variant,
+        MaterialDynamicColors::secondary_fixed,
+        MaterialDynamicColors::secondary_fixed_dim,
+        MaterialDynamicColors::on_secondary_fixed,
+        MaterialDynamicColors::on_secondary_fixed_variant,
+        MaterialDynamicColors::tertiary_fixed,
+        MaterialDynamicColors::tertiary_fixed_dim,
+        MaterialDynamicColors::on_tertiary_fixed,
+        MaterialDynamicColors::on_tertiary_fixed_variant,
+    ];
+
+    #[test]
+    fn generated_containers_are_not_disliked() {
+        // Dark yellow-greens, which `DislikeAnalyzer` dislikes, plus yellow.
+        const DISLIKED_SEEDS: [u32; 4] = [0xff6d5e00, 0xff554500, 0xff8a8647, 0xffffff00];
+        let mut failures = vec![];
+        for seed in DISLIKED_SEEDS {
+            for variant in VARIANTS {
+                for contrast_level in CONTRAST_LEVELS {
+                    for is_dark in [false, true] {
+                        let scheme = DynamicScheme::new(
+                            Hct::from_int(seed),
+                            variant,
+                            is_dark,
+                            contrast_level,
+                        );
+                        for color in [
+                            MaterialDynamicColors::primary_container(),
+                            MaterialDynamicColors::secondary_container(),
+                            MaterialDynamicColors::tertiary(),
+                            MaterialDynamicColors::tertiary_container(),
+                        ] {
+                            let hct = color.get_hct(&scheme);
+                            if DislikeAnalyzer::is_disliked(&hct) {
+                                failures.push(format!(
+                                    "{:08x} {:?} contrast {} dark {}: {} is {:08x}",
+                                    seed,
+                                    variant,
+                                    contrast_level,
+                                    is_dark,
+                                    color.name,
+                                    hct.to_int()
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    /// Checks that every foreground reaches its contrast curve on each of its
+    /// backgrounds, wherever black or white would.
+    fn contrast_curve_failures(contrast_algorithm: ContrastAlgorithm) -> Vec<String> {
+        let contrast = |argb: u32, bg_argb: u32| match contrast_algorithm {
+            ContrastAlgorithm::Wcag => ratio_of_argb(argb, bg_argb),
+            ContrastAlgorithm::Apca => apca_contrast(argb, bg_argb).abs(),
+        };
+        let mut failures = vec![];
+        for seed in SEEDS {
+            for variant in VARIANTS {
+                for contrast_level in CONTRAST_LEVELS {
+                    for is_dark in [false, true] {
+                        let mut scheme = DynamicScheme::new(
+                            Hct::from_int(seed),
+                            variant,
+                            is_dark,
+                            contrast_level,
+                        );
+                        scheme.contrast_algorithm = contrast_algorithm;
+                        for foreground in FOREGROUNDS.map(|color| color()) {
+                            let ratio = foreground.contrast_curve.unwrap().get(contrast_level);
+                            let target = contrast_algorithm.target(ratio);
+                            let argb = foreground.get_argb(&scheme);
+                            let backgrounds = [foreground.background, foreground.second_background];
+                            for background in backgrounds.into_iter().flatten() {
+                                let background = background(&scheme);
+                                let bg_argb = background.get_argb(&scheme);
+                                let actual = contrast(argb, bg_argb);
+                                // The best contrast any foreground can reach on this background.
+                                let best = contrast(0xff000000, bg_argb)
+                                    .max(contrast(0xffffffff, bg_argb));
+                                if actual < target.min(best) {
+                                    failures.push(format!(
+                                        "{:08x} {:?} contrast {} dark {}: {} on {} is {:.2}, wants {:.2}",
+                                        seed, variant, contrast_level, is_dark,
+                                        foreground.name, background.name, actual, target
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        failures
+    }
+
+    #[test]
+    fn foregrounds_meet_contrast_curve() {
+        let failures = contrast_curve_failures(ContrastAlgorithm::Wcag);
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn foregrounds_meet_apca_contrast_curve() {
+        let failures = contrast_curve_failures(ContrastAlgorithm::Apca);
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
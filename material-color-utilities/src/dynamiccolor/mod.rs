@@ -0,0 +1,9 @@
+mod contrast_curve;
+mod dynamic_color;
+mod material_dynamic_colors;
+mod tone_delta_pair;
+
+pub use contrast_curve::*;
+pub use dynamic_color::*;
+pub use material_dynamic_colors::*;
+pub use tone_delta_pair::*;
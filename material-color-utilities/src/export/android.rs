@@ -0,0 +1,207 @@
+use std::fmt::Write;
+
+use crate::{scheme::Scheme, utils::string_utils::hex_from_argb};
+
+use super::names::camel_case;
+
+/// Writes a scheme as an Android color resource file, with a color named
+/// md_theme_<role> for every role, ex. md_theme_on_primary.
+///
+/// The light scheme belongs in values/colors.xml and the dark scheme in
+/// values-night/colors.xml, so the same resource names switch with the
+/// system theme.
+///
+/// # Arguments
+///
+/// * `scheme` - The scheme to write.
+///
+/// # Returns
+///
+/// The contents of the colors.xml file.
+pub fn android_colors_xml(scheme: &Scheme) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    for (role, argb) in scheme.roles() {
+        writeln!(
+            xml,
+            "    <color name=\"md_theme_{}\">{}</color>",
+            role,
+            hex_from_argb(argb).to_uppercase()
+        )
+        .unwrap();
+    }
+    xml.push_str("</resources>\n");
+    xml
+}
+
+/// Writes the colors of a light and dark scheme as Jetpack Compose `Color`
+/// values, ex. `onPrimaryLight` and `onPrimaryDark`. See `compose_theme_kt`.
+///
+/// # Arguments
+///
+/// * `package` - The Kotlin package of the file, ex. "com.example.ui.theme".
+/// * `light` - The light scheme.
+/// * `dark` - The dark scheme.
+///
+/// # Returns
+///
+/// The contents of the Color.kt file.
+pub fn compose_color_kt(package: &str, light: &Scheme, dark: &Scheme) -> String {
+    let mut kotlin = format!(
+        "package {}\n\nimport androidx.compose.ui.graphics.Color\n",
+        package
+    );
+    for (scheme, suffix) in [(light, "Light"), (dark, "Dark")] {
+        kotlin.push('\n');
+        for (role, argb) in scheme.roles() {
+            writeln!(
+                kotlin,
+                "val {}{} = Color(0x{:08X})",
+                camel_case(role),
+                suffix,
+                argb
+            )
+            .unwrap();
+        }
+    }
+    kotlin
+}
+
+/// Writes a Jetpack Compose theme that uses the colors of `compose_color_kt`
+/// through `lightColorScheme(...)` and `darkColorScheme(...)`.
+///
+/// Every role is passed to the `ColorScheme` parameter of the same name,
+/// which needs Compose Material 3 1.4 or later for the fixed roles. Compose
+/// has no shadow role, and its background roles are the surface roles.
+///
+/// # Arguments
+///
+/// * `package` - The Kotlin package of the file, the same as Color.kt's.
+/// * `theme_name` - The name of the theme composable, ex. "AppTheme".
+///
+/// # Returns
+///
+/// The contents of the Theme.kt file.
+pub fn compose_theme_kt(package: &str, theme_name: &str) -> String {
+    let mut kotlin = format!(
+        "package {}\n\n\
+         import androidx.compose.foundation.isSystemInDarkTheme\n\
+         import androidx.compose.material3.MaterialTheme\n\
+         import androidx.compose.material3.darkColorScheme\n\
+         import androidx.compose.material3.lightColorScheme\n\
+         import androidx.compose.runtime.Composable\n",
+        package
+    );
+    for (function, suffix) in [("lightColorScheme", "Light"), ("darkColorScheme", "Dark")] {
+        writeln!(
+            kotlin,
+            "\nprivate val {}Scheme = {}(",
+            suffix.to_lowercase(),
+            function
+        )
+        .unwrap();
+        for role in Scheme::ROLE_NAMES {
+            if *role == "shadow" {
+                continue;
+            }
+            let name = camel_case(role);
+            writeln!(kotlin, "    {} = {}{},", name, name, suffix).unwrap();
+        }
+        writeln!(kotlin, "    background = surface{},", suffix).unwrap();
+        writeln!(kotlin, "    onBackground = onSurface{},", suffix).unwrap();
+        kotlin.push_str(")\n");
+    }
+    write!(
+        kotlin,
+        "\n@Composable\n\
+         fun {}(\n    \
+             darkTheme: Boolean = isSystemInDarkTheme(),\n    \
+             content: @Composable () -> Unit,\n\
+         ) {{\n    \
+             val colorScheme = if (darkTheme) darkScheme else lightScheme\n    \
+             MaterialTheme(colorScheme = colorScheme, content = content)\n\
+         }}\n",
+        theme_name
+    )
+    .unwrap();
+    kotlin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palettes::CorePalette;
+
+    fn schemes() -> (Scheme, Scheme) {
+        let core = CorePalette::of(0xff4285f4);
+        (
+            Scheme::light_from_core_palette(&core),
+            Scheme::dark_from_core_palette(&core),
+        )
+    }
+
+    #[test]
+    fn colors_xml_has_one_color_per_role() {
+        let (light, _) = schemes();
+
+        let xml = android_colors_xml(&light);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n"));
+        assert!(xml.ends_with("</resources>\n"));
+        assert_eq!(xml.matches("<color ").count(), Scheme::ROLE_NAMES.len());
+        for (role, argb) in light.roles() {
+            let line = format!(
+                "    <color name=\"md_theme_{}\">{}</color>\n",
+                role,
+                hex_from_argb(argb).to_uppercase()
+            );
+            assert_eq!(xml.matches(&line).count(), 1, "{line}");
+        }
+    }
+
+    #[test]
+    fn color_kt_has_light_and_dark_value_per_role() {
+        let (light, dark) = schemes();
+
+        let kotlin = compose_color_kt("com.example.ui.theme", &light, &dark);
+
+        assert!(kotlin.starts_with("package com.example.ui.theme\n"));
+        assert_eq!(kotlin.matches("val ").count(), 2 * Scheme::ROLE_NAMES.len());
+        assert!(kotlin.contains(&format!(
+            "val inverseOnSurfaceLight = Color(0x{:08X})\n",
+            light.inverse_on_surface
+        )));
+        assert!(kotlin.contains(&format!(
+            "val onPrimaryContainerDark = Color(0x{:08X})\n",
+            dark.on_primary_container
+        )));
+    }
+
+    #[test]
+    fn theme_kt_passes_every_compose_role_once() {
+        let kotlin = compose_theme_kt("com.example.ui.theme", "AppTheme");
+
+        assert!(kotlin.starts_with("package com.example.ui.theme\n"));
+        assert!(kotlin.contains("\nfun AppTheme(\n"));
+        for (function, suffix) in [("lightColorScheme", "Light"), ("darkColorScheme", "Dark")] {
+            let start = kotlin.find(&format!("{}(\n", function)).unwrap();
+            let end = start + kotlin[start..].find("\n)\n").unwrap();
+            let arguments: Vec<&str> = kotlin[start..end].lines().skip(1).collect();
+
+            // Every role but shadow, plus background and onBackground.
+            assert_eq!(arguments.len(), Scheme::ROLE_NAMES.len() - 1 + 2);
+            assert!(!arguments.iter().any(|line| line.contains("shadow")));
+            for (name, value) in [
+                ("background", "surface"),
+                ("onBackground", "onSurface"),
+                ("inverseOnSurface", "inverseOnSurface"),
+            ] {
+                let prefix = format!("    {} = ", name);
+                let matching: Vec<_> = arguments
+                    .iter()
+                    .filter(|line| line.starts_with(&prefix))
+                    .collect();
+                assert_eq!(matching, [&format!("{}{}{},", prefix, value, suffix)]);
+            }
+        }
+    }
+}
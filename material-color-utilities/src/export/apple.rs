@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use crate::{
+    scheme::Scheme,
+    utils::color_utils::{blue_from_argb, green_from_argb, red_from_argb},
+};
+
+use super::names::camel_case;
+
+/// Writes a light and dark scheme as an Xcode asset catalog, with a color set
+/// for every role that has a light and a dark appearance, ex.
+/// onPrimary.colorset. UIKit loads them with `UIColor(named:)`, and SwiftUI
+/// with `Color(_:)` or `swift_color_extension`.
+///
+/// # Arguments
+///
+/// * `light` - The light scheme.
+/// * `dark` - The dark scheme.
+///
+/// # Returns
+///
+/// The path of every file of the catalog, relative to its .xcassets
+/// directory, and the file's contents.
+pub fn asset_catalog_files(light: &Scheme, dark: &Scheme) -> Vec<(String, String)> {
+    let mut files = vec![(String::from("Contents.json"), format!("{{\n{}\n}}\n", INFO))];
+    for ((role, light_argb), (_, dark_argb)) in light.roles().into_iter().zip(dark.roles()) {
+        let contents = format!(
+            r#"{{
+  "colors" : [
+    {{
+{}
+    }},
+    {{
+      "appearances" : [
+        {{
+          "appearance" : "luminosity",
+          "value" : "dark"
+        }}
+      ],
+{}
+    }}
+  ],
+{}
+}}
+"#,
+            color_entry(light_argb),
+            color_entry(dark_argb),
+            INFO
+        );
+        files.push((
+            format!("{}.colorset/Contents.json", camel_case(role)),
+            contents,
+        ));
+    }
+    files
+}
+
+/// The info every Contents.json of an asset catalog ends with.
+const INFO: &str = r#"  "info" : {
+    "author" : "xcode",
+    "version" : 1
+  }"#;
+
+/// Writes the color of one appearance of a color set.
+fn color_entry(argb: u32) -> String {
+    format!(
+        r#"      "color" : {{
+        "color-space" : "srgb",
+        "components" : {{
+          "alpha" : "1.000",
+          "blue" : "0x{:02X}",
+          "green" : "0x{:02X}",
+          "red" : "0x{:02X}"
+        }}
+      }},
+      "idiom" : "universal""#,
+        blue_from_argb(argb),
+        green_from_argb(argb),
+        red_from_argb(argb)
+    )
+}
+
+/// Writes a Swift extension on SwiftUI's `Color` with a color for every role
+/// from the color sets of `asset_catalog_files`, ex. `Color.Theme.onPrimary`.
+///
+/// The colors are nested in `Theme` because `Color` already has `primary`
+/// and `secondary`.
+///
+/// # Returns
+///
+/// The contents of the Swift file.
+pub fn swift_color_extension() -> String {
+    let mut swift = String::from("import SwiftUI\n\nextension Color {\n    enum Theme {\n");
+    for role in Scheme::ROLE_NAMES {
+        let name = camel_case(role);
+        writeln!(swift, "        static let {} = Color(\"{}\")", name, name).unwrap();
+    }
+    swift.push_str("    }\n}\n");
+    swift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palettes::CorePalette;
+
+    fn schemes() -> (Scheme, Scheme) {
+        let core = CorePalette::of(0xff4285f4);
+        (
+            Scheme::light_from_core_palette(&core),
+            Scheme::dark_from_core_palette(&core),
+        )
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn color_sets_hold_light_and_dark_colors() {
+        use serde_json::Value;
+
+        /// Reads the ARGB color of one entry of a color set.
+        fn argb_of(entry: &Value) -> u32 {
+            let components = &entry["color"]["components"];
+            assert_eq!(entry["color"]["color-space"], "srgb");
+            assert_eq!(components["alpha"], "1.000");
+            let component = |name: &str| {
+                let hex = components[name].as_str().unwrap();
+                u32::from_str_radix(hex.strip_prefix("0x").unwrap(), 16).unwrap()
+            };
+            0xff000000 | component("red") << 16 | component("green") << 8 | component("blue")
+        }
+
+        let (light, dark) = schemes();
+        let files = asset_catalog_files(&light, &dark);
+
+        assert_eq!(files.len(), Scheme::ROLE_NAMES.len() + 1);
+        for (path, contents) in &files {
+            let json: Value = serde_json::from_str(contents).unwrap();
+            assert_eq!(json["info"]["version"], 1, "{path}");
+        }
+        let roles = light.roles().into_iter().zip(dark.roles());
+        for ((path, contents), ((role, light_argb), (_, dark_argb))) in files[1..].iter().zip(roles)
+        {
+            assert_eq!(
+                *path,
+                format!("{}.colorset/Contents.json", camel_case(role))
+            );
+            let json: Value = serde_json::from_str(contents).unwrap();
+            let colors = json["colors"].as_array().unwrap();
+            assert_eq!(colors.len(), 2, "{path}");
+            assert!(colors[0].get("appearances").is_none(), "{path}");
+            assert_eq!(
+                colors[1]["appearances"],
+                serde_json::json!([{ "appearance": "luminosity", "value": "dark" }]),
+                "{path}"
+            );
+            assert_eq!(argb_of(&colors[0]), light_argb, "{path}");
+            assert_eq!(argb_of(&colors[1]), dark_argb, "{path}");
+        }
+    }
+
+    #[test]
+    fn swift_names_match_color_sets() {
+        let (light, dark) = schemes();
+        let color_sets: Vec<String> = asset_catalog_files(&light, &dark)
+            .into_iter()
+            .filter_map(|(path, _)| Some(path.strip_suffix(".colorset/Contents.json")?.to_string()))
+            .collect();
+
+        let swift = swift_color_extension();
+        let names: Vec<&str> = swift
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("static let "))
+            .map(|line| {
+                let (name, value) = line.split_once(" = ").unwrap();
+                assert_eq!(value, format!("Color(\"{}\")", name));
+                name
+            })
+            .collect();
+
+        assert_eq!(names, color_sets);
+        assert!(names.contains(&"onPrimaryContainer"));
+    }
+}
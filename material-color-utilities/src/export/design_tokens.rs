@@ -0,0 +1,209 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{
+    palettes::{CorePalette, TonalPalette},
+    scheme::Scheme,
+    utils::string_utils::hex_from_argb,
+};
+
+/// Writes schemes and their palettes as design tokens in the JSON format of
+/// the W3C Design Tokens Community Group (DTCG), as read by ex. Style
+/// Dictionary.
+///
+/// Every tone from 0 to 100 of each palette is a token, ex.
+/// `palette.primary.40`. Every role of the light and dark schemes is a token,
+/// ex. `scheme.light.on-primary`, that references the palette token of the
+/// same color, or holds the color itself when no whole tone of the role's
+/// palette matches, as with contrast adjusted schemes.
+///
+/// # Arguments
+///
+/// * `core` - The palettes the schemes were built from.
+/// * `light` - The light scheme.
+/// * `dark` - The dark scheme.
+///
+/// # Returns
+///
+/// The tokens as pretty-printed JSON.
+pub fn design_tokens(core: &CorePalette, light: &Scheme, dark: &Scheme) -> String {
+    let palettes = [
+        ("primary", &core.a1),
+        ("secondary", &core.a2),
+        ("tertiary", &core.a3),
+        ("neutral", &core.n1),
+        ("neutral-variant", &core.n2),
+        ("error", &core.error),
+    ];
+    let palette_group = Token::Group(
+        Some("color"),
+        palettes
+            .iter()
+            .map(|(name, palette)| {
+                let tones = (0..=100)
+                    .map(|tone| {
+                        (
+                            tone.to_string(),
+                            Token::Color(hex_from_argb(palette.tone(tone))),
+                        )
+                    })
+                    .collect();
+                (name.to_string(), Token::Group(None, tones))
+            })
+            .collect(),
+    );
+    let scheme_group = |scheme: &Scheme| {
+        let roles = scheme
+            .roles()
+            .into_iter()
+            .map(|(role, argb)| {
+                let name = palette_of_role(role);
+                let palette = palettes.iter().find(|(n, _)| *n == name).unwrap().1;
+                let value = match tone_of(palette, argb) {
+                    Some(tone) => format!("{{palette.{}.{}}}", name, tone),
+                    None => hex_from_argb(argb),
+                };
+                (role.replace('_', "-"), Token::Color(value))
+            })
+            .collect();
+        Token::Group(None, roles)
+    };
+    let tokens = Token::Group(
+        None,
+        vec![
+            ("palette".to_string(), palette_group),
+            (
+                "scheme".to_string(),
+                Token::Group(
+                    Some("color"),
+                    vec![
+                        ("light".to_string(), scheme_group(light)),
+                        ("dark".to_string(), scheme_group(dark)),
+                    ],
+                ),
+            ),
+        ],
+    );
+    serde_json::to_string_pretty(&tokens).expect("tokens always serialize")
+}
+
+/// Returns the name of the palette a role of a `Scheme` takes its color from.
+fn palette_of_role(role: &str) -> &'static str {
+    if role.contains("primary") || role == "surface_tint" {
+        "primary"
+    } else if role.contains("secondary") {
+        "secondary"
+    } else if role.contains("tertiary") {
+        "tertiary"
+    } else if role.contains("error") {
+        "error"
+    } else if role.contains("variant") || role.contains("outline") {
+        "neutral-variant"
+    } else {
+        "neutral"
+    }
+}
+
+/// Returns the whole tone of `palette` whose color is `argb`, if any.
+fn tone_of(palette: &TonalPalette, argb: u32) -> Option<u32> {
+    (0..=100).find(|&tone| palette.tone(tone) == argb)
+}
+
+/// A design token, or a group of them.
+enum Token {
+    /// A group of tokens, and the `$type` they all share.
+    Group(Option<&'static str>, Vec<(String, Token)>),
+
+    /// A color token, with its value as a hex code or a reference to another
+    /// token.
+    Color(String),
+}
+
+impl Serialize for Token {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Token::Group(token_type, tokens) => {
+                if let Some(token_type) = token_type {
+                    map.serialize_entry("$type", token_type)?;
+                }
+                for (name, token) in tokens {
+                    map.serialize_entry(name, token)?;
+                }
+            }
+            Token::Color(value) => map.serialize_entry("$value", value)?,
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Checks every scheme token resolves to its role's color, and returns
+    /// how many hold the color itself instead of a palette reference.
+    fn check_scheme_tokens(tokens: &Value, mode: &str, scheme: &Scheme) -> usize {
+        let group = tokens["scheme"][mode].as_object().unwrap();
+        assert_eq!(group.len(), scheme.roles().len());
+        let mut literals = 0;
+        for (role, argb) in scheme.roles() {
+            let token = &group[&role.replace('_', "-")];
+            assert!(token.get("$type").is_none(), "{mode}.{role} repeats $type");
+            let value = token["$value"].as_str().unwrap();
+            let hex = match value.strip_prefix("{palette.") {
+                Some(alias) => {
+                    let (name, tone) = alias.strip_suffix('}').unwrap().split_once('.').unwrap();
+                    assert_eq!(name, palette_of_role(role), "{mode}.{role}");
+                    tokens["palette"][name][tone]["$value"].as_str().unwrap()
+                }
+                None => {
+                    // Only colors off the whole tones of the palette.
+                    let palette = palette_of_role(role);
+                    assert!(tokens["palette"][palette]
+                        .as_object()
+                        .unwrap()
+                        .values()
+                        .all(|token| token["$value"] != value));
+                    literals += 1;
+                    value
+                }
+            };
+            assert_eq!(hex, hex_from_argb(argb), "{mode}.{role} is {value}");
+        }
+        literals
+    }
+
+    #[test]
+    fn scheme_tokens_resolve_to_role_colors() {
+        let core = CorePalette::of(0xff4285f4);
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+
+        let tokens: Value = serde_json::from_str(&design_tokens(&core, &light, &dark)).unwrap();
+
+        assert_eq!(tokens["palette"]["$type"], "color");
+        assert_eq!(tokens["scheme"]["$type"], "color");
+        assert_eq!(
+            tokens["palette"]["neutral-variant"]["50"]["$value"],
+            hex_from_argb(core.n2.tone(50))
+        );
+        assert!(tokens["palette"]["primary"]["40"].get("$type").is_none());
+        // Schemes of whole tones only reference the palettes.
+        assert_eq!(check_scheme_tokens(&tokens, "light", &light), 0);
+        assert_eq!(check_scheme_tokens(&tokens, "dark", &dark), 0);
+    }
+
+    #[test]
+    fn contrast_adjusted_roles_hold_their_color() {
+        let core = CorePalette::of(0xff4285f4);
+        let light = Scheme::light_from_core_palette_with_contrast(&core, 1.0);
+        let dark = Scheme::dark_from_core_palette_with_contrast(&core, 1.0);
+
+        let tokens: Value = serde_json::from_str(&design_tokens(&core, &light, &dark)).unwrap();
+
+        assert!(check_scheme_tokens(&tokens, "light", &light) > 0);
+        assert!(check_scheme_tokens(&tokens, "dark", &dark) > 0);
+    }
+}
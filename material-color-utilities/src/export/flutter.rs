@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use crate::scheme::Scheme;
+
+use super::names::camel_case;
+
+/// Writes a light and dark scheme as Flutter `ColorScheme` constants named
+/// `lightColorScheme` and `darkColorScheme`, for a color_schemes.g.dart file.
+///
+/// Every role is passed to the `ColorScheme` parameter of the same name,
+/// except that:
+/// - inverse_on_surface is passed as `onInverseSurface`.
+/// - surface_variant is left out, as Flutter deprecated `surfaceVariant` in
+///   favor of `surfaceContainerHighest`.
+///
+/// The fixed roles need a Flutter version whose `ColorScheme` has them.
+///
+/// # Arguments
+///
+/// * `light` - The light scheme.
+/// * `dark` - The dark scheme.
+///
+/// # Returns
+///
+/// The contents of the Dart file.
+pub fn flutter_color_schemes_dart(light: &Scheme, dark: &Scheme) -> String {
+    let mut dart = String::from(
+        "// GENERATED CODE - DO NOT MODIFY BY HAND\n\nimport 'package:flutter/material.dart';\n",
+    );
+    for (scheme, brightness) in [(light, "light"), (dark, "dark")] {
+        writeln!(
+            dart,
+            "\nconst ColorScheme {}ColorScheme = ColorScheme(\n  brightness: Brightness.{},",
+            brightness, brightness
+        )
+        .unwrap();
+        for (role, argb) in scheme.roles() {
+            if let Some(name) = flutter_name(role) {
+                writeln!(dart, "  {}: Color(0x{:08X}),", name, argb).unwrap();
+            }
+        }
+        dart.push_str(");\n");
+    }
+    dart
+}
+
+/// Returns the name of the Flutter `ColorScheme` parameter for a role of a
+/// `Scheme`, if Flutter has one.
+fn flutter_name(role: &str) -> Option<String> {
+    match role {
+        "inverse_on_surface" => Some(String::from("onInverseSurface")),
+        "surface_variant" => None,
+        _ => Some(camel_case(role)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palettes::CorePalette;
+
+    /// Returns the arguments of the `ColorScheme` constant named `name`.
+    fn arguments<'a>(dart: &'a str, name: &str) -> Vec<&'a str> {
+        let start = dart
+            .find(&format!("const ColorScheme {} = ColorScheme(\n", name))
+            .unwrap();
+        let end = start + dart[start..].find("\n);\n").unwrap();
+        dart[start..end].lines().skip(1).collect()
+    }
+
+    #[test]
+    fn color_schemes_map_roles_to_flutter_names() {
+        let core = CorePalette::of(0xff4285f4);
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+
+        let dart = flutter_color_schemes_dart(&light, &dark);
+
+        assert!(dart.starts_with("// GENERATED CODE - DO NOT MODIFY BY HAND\n"));
+        for (name, brightness, scheme) in [
+            ("lightColorScheme", "light", &light),
+            ("darkColorScheme", "dark", &dark),
+        ] {
+            let arguments = arguments(&dart, name);
+            let brightness_line = format!("  brightness: Brightness.{},", brightness);
+            assert_eq!(arguments[0], brightness_line);
+            // Every role but surface_variant, after the brightness.
+            assert_eq!(arguments.len(), 1 + Scheme::ROLE_NAMES.len() - 1);
+            assert!(!arguments.iter().any(|line| line.contains("surfaceVariant")));
+            assert!(!arguments
+                .iter()
+                .any(|line| line.contains("inverseOnSurface")));
+            let inverse_on_surface = format!(
+                "  onInverseSurface: Color(0x{:08X}),",
+                scheme.inverse_on_surface
+            );
+            assert!(arguments.contains(&inverse_on_surface.as_str()));
+            for (role, argb) in scheme.roles() {
+                if let Some(argument) = flutter_name(role) {
+                    let line = format!("  {}: Color(0x{:08X}),", argument, argb);
+                    assert_eq!(
+                        arguments.iter().filter(|l| **l == line).count(),
+                        1,
+                        "{line}"
+                    );
+                }
+            }
+        }
+        assert_eq!(dart.matches("brightness: ").count(), 2);
+    }
+}
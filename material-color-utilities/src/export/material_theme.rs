@@ -0,0 +1,425 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    palettes::{CorePalette, CorePaletteColors, TonalPalette},
+    scheme::Scheme,
+    utils::{
+        color_utils::Argb,
+        string_utils::{hex_from_argb, try_argb_from_hex},
+    },
+};
+
+/// The tones of each palette in a Material Theme Builder export.
+pub const MATERIAL_THEME_TONES: [u32; 18] = [
+    0, 5, 10, 15, 20, 25, 30, 35, 40, 50, 60, 70, 80, 90, 95, 98, 99, 100,
+];
+
+/// Material Theme Builder's name for each role of a `Scheme`, in the order it
+/// exports them.
+const SCHEME_KEYS: [(&str, &str); 49] = [
+    ("primary", "primary"),
+    ("surfaceTint", "surface_tint"),
+    ("onPrimary", "on_primary"),
+    ("primaryContainer", "primary_container"),
+    ("onPrimaryContainer", "on_primary_container"),
+    ("secondary", "secondary"),
+    ("onSecondary", "on_secondary"),
+    ("secondaryContainer", "secondary_container"),
+    ("onSecondaryContainer", "on_secondary_container"),
+    ("tertiary", "tertiary"),
+    ("onTertiary", "on_tertiary"),
+    ("tertiaryContainer", "tertiary_container"),
+    ("onTertiaryContainer", "on_tertiary_container"),
+    ("error", "error"),
+    ("onError", "on_error"),
+    ("errorContainer", "error_container"),
+    ("onErrorContainer", "on_error_container"),
+    ("background", "surface"),
+    ("onBackground", "on_surface"),
+    ("surface", "surface"),
+    ("onSurface", "on_surface"),
+    ("surfaceVariant", "surface_variant"),
+    ("onSurfaceVariant", "on_surface_variant"),
+    ("outline", "outline"),
+    ("outlineVariant", "outline_variant"),
+    ("shadow", "shadow"),
+    ("scrim", "scrim"),
+    ("inverseSurface", "inverse_surface"),
+    ("inverseOnSurface", "inverse_on_surface"),
+    ("inversePrimary", "inverse_primary"),
+    ("primaryFixed", "primary_fixed"),
+    ("onPrimaryFixed", "on_primary_fixed"),
+    ("primaryFixedDim", "primary_fixed_dim"),
+    ("onPrimaryFixedVariant", "on_primary_fixed_variant"),
+    ("secondaryFixed", "secondary_fixed"),
+    ("onSecondaryFixed", "on_secondary_fixed"),
+    ("secondaryFixedDim", "secondary_fixed_dim"),
+    ("onSecondaryFixedVariant", "on_secondary_fixed_variant"),
+    ("tertiaryFixed", "tertiary_fixed"),
+    ("onTertiaryFixed", "on_tertiary_fixed"),
+    ("tertiaryFixedDim", "tertiary_fixed_dim"),
+    ("onTertiaryFixedVariant", "on_tertiary_fixed_variant"),
+    ("surfaceDim", "surface_dim"),
+    ("surfaceBright", "surface_bright"),
+    ("surfaceContainerLowest", "surface_container_lowest"),
+    ("surfaceContainerLow", "surface_container_low"),
+    ("surfaceContainer", "surface_container"),
+    ("surfaceContainerHigh", "surface_container_high"),
+    ("surfaceContainerHighest", "surface_container_highest"),
+];
+
+/// A theme in the JSON format Material Theme Builder exports, so that design
+/// and engineering can share one source of truth.
+///
+/// Importing and exporting a theme keeps every color as it is, including
+/// colors a designer adjusted by hand.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaterialTheme {
+    /// Free text describing the theme, ex. "TYPE: CUSTOM".
+    #[serde(default)]
+    pub description: String,
+
+    /// The color the theme is generated from.
+    #[serde(with = "hex")]
+    pub seed: Argb,
+
+    /// The colors the palettes are generated from.
+    #[serde(with = "core_colors")]
+    pub core_colors: CorePaletteColors,
+
+    /// Colors a product uses in addition to the scheme roles, ex. a brand
+    /// color.
+    #[serde(default)]
+    pub extended_colors: Vec<ExtendedColor>,
+
+    pub schemes: MaterialThemeSchemes,
+    pub palettes: MaterialThemePalettes,
+}
+
+/// The light and dark schemes of a `MaterialTheme` at standard, medium and
+/// high contrast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialThemeSchemes {
+    #[serde(with = "scheme")]
+    pub light: Scheme,
+    #[serde(rename = "light-medium-contrast", with = "scheme")]
+    pub light_medium_contrast: Scheme,
+    #[serde(rename = "light-high-contrast", with = "scheme")]
+    pub light_high_contrast: Scheme,
+    #[serde(with = "scheme")]
+    pub dark: Scheme,
+    #[serde(rename = "dark-medium-contrast", with = "scheme")]
+    pub dark_medium_contrast: Scheme,
+    #[serde(rename = "dark-high-contrast", with = "scheme")]
+    pub dark_high_contrast: Scheme,
+}
+
+/// The palettes of a `MaterialTheme`, as maps from tone to ARGB color.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialThemePalettes {
+    #[serde(with = "tones")]
+    pub primary: BTreeMap<u32, Argb>,
+    #[serde(with = "tones")]
+    pub secondary: BTreeMap<u32, Argb>,
+    #[serde(with = "tones")]
+    pub tertiary: BTreeMap<u32, Argb>,
+    #[serde(with = "tones")]
+    pub neutral: BTreeMap<u32, Argb>,
+    #[serde(rename = "neutral-variant", with = "tones")]
+    pub neutral_variant: BTreeMap<u32, Argb>,
+}
+
+/// A color a product uses in addition to the scheme roles.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedColor {
+    pub name: String,
+
+    #[serde(with = "hex")]
+    pub color: Argb,
+
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether the color is shifted towards the primary color, see
+    /// `Blend::harmonize`.
+    #[serde(default)]
+    pub harmonized: bool,
+}
+
+impl MaterialTheme {
+    /// Generates a theme from a set of colors, the way Material Theme Builder
+    /// does.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The colors to generate the palettes from. The primary
+    ///   color is the seed.
+    pub fn from_colors(colors: CorePaletteColors) -> MaterialTheme {
+        MaterialTheme::from_core_palette(colors, &CorePalette::from_colors(colors))
+    }
+
+    /// Creates a theme from a core palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The colors `core` was generated from. The primary color
+    ///   is the seed.
+    /// * `core` - The palettes to sample tones and build schemes from.
+    pub fn from_core_palette(colors: CorePaletteColors, core: &CorePalette) -> MaterialTheme {
+        let light =
+            |contrast_level| Scheme::light_from_core_palette_with_contrast(core, contrast_level);
+        let dark =
+            |contrast_level| Scheme::dark_from_core_palette_with_contrast(core, contrast_level);
+        MaterialTheme {
+            description: String::from("TYPE: CUSTOM"),
+            seed: colors.primary,
+            core_colors: colors,
+            extended_colors: Vec::new(),
+            schemes: MaterialThemeSchemes {
+                light: light(0.0),
+                light_medium_contrast: light(0.5),
+                light_high_contrast: light(1.0),
+                dark: dark(0.0),
+                dark_medium_contrast: dark(0.5),
+                dark_high_contrast: dark(1.0),
+            },
+            palettes: MaterialThemePalettes {
+                primary: sample_tones(&core.a1),
+                secondary: sample_tones(&core.a2),
+                tertiary: sample_tones(&core.a3),
+                neutral: sample_tones(&core.n1),
+                neutral_variant: sample_tones(&core.n2),
+            },
+        }
+    }
+
+    /// Reads a theme exported by Material Theme Builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - Contents of the exported material-theme.json file.
+    pub fn from_json(json: &str) -> Result<MaterialTheme, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the theme as pretty-printed JSON that Material Theme Builder
+    /// can import.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a theme always serializes")
+    }
+}
+
+fn sample_tones(palette: &TonalPalette) -> BTreeMap<u32, Argb> {
+    MATERIAL_THEME_TONES
+        .iter()
+        .map(|&tone| (tone, Argb(palette.tone(tone))))
+        .collect()
+}
+
+/// A color as an uppercase #RRGGBB string, as Material Theme Builder writes
+/// them.
+struct Hex(u32);
+
+impl Serialize for Hex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex_from_argb(self.0).to_uppercase())
+    }
+}
+
+impl From<Argb> for Hex {
+    fn from(argb: Argb) -> Hex {
+        Hex(argb.0)
+    }
+}
+
+impl From<Hex> for Argb {
+    fn from(hex: Hex) -> Argb {
+        Argb(hex.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        try_argb_from_hex(&hex).map(Hex).map_err(D::Error::custom)
+    }
+}
+
+mod hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(argb: &Argb, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex::from(*argb).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Argb, D::Error> {
+        Hex::deserialize(deserializer).map(Argb::from)
+    }
+}
+
+mod core_colors {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CoreColors {
+        primary: Hex,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        secondary: Option<Hex>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tertiary: Option<Hex>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<Hex>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        neutral: Option<Hex>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        neutral_variant: Option<Hex>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        colors: &CorePaletteColors,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        CoreColors {
+            primary: Hex::from(colors.primary),
+            secondary: colors.secondary.map(Hex::from),
+            tertiary: colors.tertiary.map(Hex::from),
+            error: colors.error.map(Hex::from),
+            neutral: colors.neutral.map(Hex::from),
+            neutral_variant: colors.neutral_variant.map(Hex::from),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CorePaletteColors, D::Error> {
+        let colors = CoreColors::deserialize(deserializer)?;
+        Ok(CorePaletteColors {
+            primary: Argb::from(colors.primary),
+            secondary: colors.secondary.map(Argb::from),
+            tertiary: colors.tertiary.map(Argb::from),
+            neutral: colors.neutral.map(Argb::from),
+            neutral_variant: colors.neutral_variant.map(Argb::from),
+            error: colors.error.map(Argb::from),
+        })
+    }
+}
+
+mod scheme {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(scheme: &Scheme, serializer: S) -> Result<S::Ok, S::Error> {
+        let roles: HashMap<_, _> = scheme.roles().into_iter().collect();
+        serializer.collect_map(
+            SCHEME_KEYS
+                .iter()
+                .map(|(key, role)| (key, Hex(roles[role]))),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scheme, D::Error> {
+        let colors = HashMap::<String, Hex>::deserialize(deserializer)?;
+        // background and onBackground repeat surface and onSurface.
+        let key_of = |role| {
+            SCHEME_KEYS
+                .iter()
+                .filter(|(key, _)| !matches!(*key, "background" | "onBackground"))
+                .find(|(_, r)| *r == role)
+                .map_or(role, |(key, _)| *key)
+        };
+        Scheme::try_from_roles(|role| colors.get(key_of(role)).map(|hex| hex.0))
+            .map_err(|role| D::Error::missing_field(key_of(role)))
+    }
+}
+
+mod tones {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        tones: &BTreeMap<u32, Argb>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(tones.iter().map(|(tone, &argb)| (tone, Hex::from(argb))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<u32, Argb>, D::Error> {
+        let tones = BTreeMap::<u32, Hex>::deserialize(deserializer)?;
+        Ok(tones
+            .into_iter()
+            .map(|(tone, hex)| (tone, Argb::from(hex)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An export laid out the way Material Theme Builder writes it: extended
+    /// colors, a `neutral-variant` palette, and a light scheme without the
+    /// `background` and `onBackground` keys.
+    const EXPORT: &str = include_str!("testdata/material-theme.json");
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut theme = MaterialTheme::from_colors(CorePaletteColors {
+            tertiary: Some(Argb(0xff7d5260)),
+            ..CorePaletteColors::from_int(0xff4285f4)
+        });
+        theme.extended_colors.push(ExtendedColor {
+            name: String::from("Brand"),
+            color: Argb(0xff1b6d00),
+            description: String::from("Checkout buttons"),
+            harmonized: true,
+        });
+
+        assert_eq!(MaterialTheme::from_json(&theme.to_json()).unwrap(), theme);
+    }
+
+    #[test]
+    fn reads_material_theme_builder_export() {
+        let theme = MaterialTheme::from_json(EXPORT).unwrap();
+
+        assert_eq!(theme.seed, Argb(0xff6750a4));
+        assert_eq!(theme.core_colors, CorePaletteColors::from_int(0xff6750a4));
+        assert_eq!(
+            theme.extended_colors,
+            vec![ExtendedColor {
+                name: String::from("Brand"),
+                color: Argb(0xff1b6d00),
+                description: String::from("Checkout buttons"),
+                harmonized: true,
+            }]
+        );
+
+        // Without a background key, the light scheme takes it from surface.
+        assert_eq!(theme.schemes.light.surface, 0xfffdf8fd);
+        assert_eq!(theme.schemes.light.on_surface, 0xff1c1b1e);
+        assert_eq!(theme.schemes.dark.primary, 0xffcfbcff);
+
+        assert_eq!(
+            theme.palettes.neutral_variant.len(),
+            MATERIAL_THEME_TONES.len()
+        );
+        assert_eq!(theme.palettes.neutral_variant[&5], Argb(0xff121017));
+        assert_eq!(theme.palettes.neutral_variant[&10], Argb(0xff1d1a22));
+    }
+
+    #[test]
+    fn rejects_export_missing_a_role() {
+        let export = EXPORT.replacen("\"onPrimary\": \"#FFFFFF\",", "", 1);
+
+        let error = MaterialTheme::from_json(&export).unwrap_err();
+
+        assert!(
+            error.to_string().contains("missing field `onPrimary`"),
+            "{error}"
+        );
+    }
+}
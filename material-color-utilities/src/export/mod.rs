@@ -0,0 +1,16 @@
+mod android;
+mod apple;
+#[cfg(feature = "json")]
+mod design_tokens;
+mod flutter;
+#[cfg(feature = "json")]
+mod material_theme;
+mod names;
+
+pub use android::*;
+pub use apple::*;
+#[cfg(feature = "json")]
+pub use design_tokens::*;
+pub use flutter::*;
+#[cfg(feature = "json")]
+pub use material_theme::*;
@@ -0,0 +1,16 @@
+// Naming conventions of the platforms themes are exported to.
+
+/// Converts the snake_case name of a `Scheme` role to camelCase, ex.
+/// "on_primary_container" to "onPrimaryContainer".
+pub(crate) fn camel_case(snake_case: &str) -> String {
+    let mut words = snake_case.split('_');
+    let mut camel_case = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel_case.extend(first.to_uppercase());
+            camel_case.push_str(chars.as_str());
+        }
+    }
+    camel_case
+}
@@ -1,4 +1,4 @@
-use crate::utils::color_utils::{argb_from_xyz, linearized};
+use crate::utils::color_utils::{argb_from_xyz, linearized, Argb};
 
 use super::viewing_conditions::{self, ViewingConditions};
 
@@ -17,7 +17,8 @@ use super::viewing_conditions::{self, ViewingConditions};
 /// For example, white under the traditional assumption of a midday sun white
 /// point is accurately measured as a slightly chromatic blue by CAM16. (roughly,
 /// hue 203, chroma 3, lightness 100)
-///
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cam16 {
     pub(crate) hue: f64,
 
@@ -37,13 +38,13 @@ pub struct Cam16 {
     s: f64,
 
     //  CAM16-UCS J coordinate
-    j_star: f64,
+    pub(crate) j_star: f64,
 
     //  CAM16-UCS a coordinate
-    a_star: f64,
+    pub(crate) a_star: f64,
 
     //  CAM16-UCS b coordinate
-    b_star: f64,
+    pub(crate) b_star: f64,
 }
 
 impl Cam16 {
@@ -125,7 +126,7 @@ impl Cam16 {
         let b = (r_a + g_a - 2.0 * b_a) / 9.0;
         let u = (20.0 * r_a + 20.0 * g_a + 21.0 * b_a) / 20.0;
         let p2 = (40.0 * r_a + 20.0 * g_a + b_a) / 20.0;
-        let atan2 = (b / a).atan();
+        let atan2 = b.atan2(a);
         let atan_degrees = (atan2 * 180.0) / std::f64::consts::PI;
         let hue = if atan_degrees < 0.0 {
             atan_degrees + 360.0
@@ -144,7 +145,7 @@ impl Cam16 {
             * (viewing_conditions.aw + 4.0)
             * viewing_conditions.f_l_root;
         let hue_prime = if hue < 20.14 { hue + 360.0 } else { hue };
-        let e_hue = 0.25 * ((hue_prime * std::f64::consts::PI) / 180.0 + 2.0).cos() + 3.8;
+        let e_hue = 0.25 * (((hue_prime * std::f64::consts::PI) / 180.0 + 2.0).cos() + 3.8);
         let p1 = (50000.0 / 13.0) * e_hue * viewing_conditions.nc * viewing_conditions.ncb;
         let t = (p1 * (a.powi(2) + b.powi(2)).sqrt()) / (u + 0.305);
         let alpha = t.powf(0.9) * (1.64 - 0.29_f64.powf(viewing_conditions.n)).powf(0.73);
@@ -263,9 +264,9 @@ impl Cam16 {
         let a = a_star;
         let b = b_star;
         let m = (a * a + b * b).sqrt();
-        let big_m = (m * 0.0228).exp() - 1.0 / 0.0228;
+        let big_m = ((m * 0.0228).exp() - 1.0) / 0.0228;
         let c = big_m / viewing_conditions.f_l_root;
-        let mut h = (b / a).atan() * (180.0 / std::f64::consts::PI);
+        let mut h = b.atan2(a) * (180.0 / std::f64::consts::PI);
         if h < 0.0 {
             h += 360.0;
         }
@@ -290,45 +291,7 @@ impl Cam16 {
     /// # Returns
     /// The ARGB representation of the color.
     pub fn viewed(&self, viewing_conditions: &ViewingConditions) -> u32 {
-        let alpha = if self.chroma == 0.0 || self.j == 0.0 {
-            0.0
-        } else {
-            self.chroma / (self.j / 100.0).sqrt()
-        };
-
-        let t = (alpha / (1.64 - (0.29f64.powf(viewing_conditions.n))).powf(0.73)).powf(1.0 / 0.9);
-        let h_rad = (self.hue * std::f64::consts::PI) / 180.0;
-
-        let e_hue = 0.25 * (h_rad.cos() + 3.8);
-        let ac = viewing_conditions.aw
-            * (self.j / 100.0).powf(1.0 / (viewing_conditions.c * viewing_conditions.z));
-        let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc * viewing_conditions.ncb;
-        let p2 = ac / viewing_conditions.nbb;
-
-        let h_sin = h_rad.sin();
-        let h_cos = h_rad.cos();
-
-        let gamma = (23.0 * (p2 + 0.305) * t) / (23.0 * p1 + 11.0 * t * h_cos + 108.0 * t * h_sin);
-        let a = gamma * h_cos;
-        let b = gamma * h_sin;
-        let r_a = (460.0 * p2 + 451.0 * a + 288.0 * b) / 1403.0;
-        let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
-        let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
-
-        let r_c_base = (27.13 * r_a.abs()) / (400.0 - r_a.abs()).max(0.0);
-        let r_c = r_a.signum() * (100.0 / viewing_conditions.fl) * r_c_base.powf(1.0 / 0.42);
-        let g_c_base = (27.13 * g_a.abs()) / (400.0 - g_a.abs()).max(0.0);
-        let g_c = g_a.signum() * (100.0 / viewing_conditions.fl) * g_c_base.powf(1.0 / 0.42);
-        let b_c_base = (27.13 * b_a.abs()) / (400.0 - b_a.abs()).max(0.0);
-        let b_c = b_a.signum() * (100.0 / viewing_conditions.fl) * b_c_base.powf(1.0 / 0.42);
-        let r_f = r_c / viewing_conditions.rgb_d[0];
-        let g_f = g_c / viewing_conditions.rgb_d[1];
-        let b_f = b_c / viewing_conditions.rgb_d[2];
-
-        let x = 1.86206786 * r_f - 1.01125463 * g_f + 0.14918677 * b_f;
-        let y = 0.38752654 * r_f + 0.62144744 * g_f - 0.00897398 * b_f;
-        let z = -0.01584150 * r_f - 0.03412294 * g_f + 1.04996444 * b_f;
-
+        let [x, y, z] = self.xyz_in_viewing_conditions(viewing_conditions);
         argb_from_xyz(x, y, z)
     }
 
@@ -401,7 +364,7 @@ impl Cam16 {
 
         // CAM16-UCS components
         let j_star = (1.0 + 100.0 * 0.007) * j / (1.0 + 0.007 * j);
-        let m_star = (m * 0.0228).ln() / 0.0228;
+        let m_star = (1.0 + 0.0228 * m).ln() / 0.0228;
         let a_star = m_star * hue_radians.cos();
         let b_star = m_star * hue_radians.sin();
 
@@ -429,7 +392,7 @@ impl Cam16 {
         let t = (alpha / (1.64 - (0.29f64).powf(viewing_conditions.n)).powf(0.73)).powf(1.0 / 0.9);
         let h_rad = self.hue.to_radians();
 
-        let e_hue = 0.25 * (h_rad.cos() + 3.8);
+        let e_hue = 0.25 * ((h_rad + 2.0).cos() + 3.8);
         let ac = viewing_conditions.aw
             * (self.j / 100.0).powf(1.0 / (viewing_conditions.c * viewing_conditions.z));
         let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc * viewing_conditions.ncb;
@@ -446,11 +409,11 @@ impl Cam16 {
         let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
         let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
 
-        let r_c_base = (27.13 * r_a.abs()) / (400.0 - r_a.abs()).max(0.0);
+        let r_c_base = ((27.13 * r_a.abs()) / (400.0 - r_a.abs())).max(0.0);
         let r_c = r_a.signum() * (100.0 / viewing_conditions.fl) * r_c_base.powf(1.0 / 0.42);
-        let g_c_base = (27.13 * g_a.abs()) / (400.0 - g_a.abs()).max(0.0);
+        let g_c_base = ((27.13 * g_a.abs()) / (400.0 - g_a.abs())).max(0.0);
         let g_c = g_a.signum() * (100.0 / viewing_conditions.fl) * g_c_base.powf(1.0 / 0.42);
-        let b_c_base = (27.13 * b_a.abs()) / (400.0 - b_a.abs()).max(0.0);
+        let b_c_base = ((27.13 * b_a.abs()) / (400.0 - b_a.abs())).max(0.0);
         let b_c = b_a.signum() * (100.0 / viewing_conditions.fl) * b_c_base.powf(1.0 / 0.42);
         let r_f = r_c / viewing_conditions.rgb_d[0];
         let g_f = g_c / viewing_conditions.rgb_d[1];
@@ -463,3 +426,46 @@ impl Cam16 {
         [x, y, z]
     }
 }
+
+impl From<Argb> for Cam16 {
+    /// Converts the color assuming it was viewed in default viewing
+    /// conditions.
+    fn from(argb: Argb) -> Cam16 {
+        Cam16::from_int(argb.0)
+    }
+}
+
+impl From<&Cam16> for Argb {
+    /// Converts the color assuming it was viewed in default viewing
+    /// conditions.
+    fn from(cam: &Cam16) -> Argb {
+        Argb(cam.to_int())
+    }
+}
+
+impl From<Cam16> for Argb {
+    /// Converts the color assuming it was viewed in default viewing
+    /// conditions.
+    fn from(cam: Cam16) -> Argb {
+        Argb(cam.to_int())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ucs_round_trips() {
+        for argb in [
+            0xffff0000, 0xff00ff00, 0xff0000ff, 0xff4285f4, 0xff808080, 0xffffffff,
+        ] {
+            let cam = Cam16::from_int(argb);
+            let round_trip = Cam16::from_ucs(cam.j_star, cam.a_star, cam.b_star);
+            assert!((round_trip.j - cam.j).abs() < 1e-6, "{argb:08x}");
+            assert!((round_trip.chroma - cam.chroma).abs() < 1e-6, "{argb:08x}");
+            assert!((round_trip.hue - cam.hue).abs() < 1e-6, "{argb:08x}");
+            assert_eq!(round_trip.to_int(), argb);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::utils::color_utils::{lstar_from_argb, lstar_from_y};
+use crate::utils::color_utils::{lstar_from_argb, lstar_from_y, Argb};
 
 use super::{cam16::Cam16, solve_to_int, viewing_conditions::ViewingConditions};
 
@@ -19,7 +19,7 @@ use super::{cam16::Cam16, solve_to_int, viewing_conditions::ViewingConditions};
 ///  HCT, hue, chroma, and tone. A color system that provides a perceptually
 /// accurate color measurement system that can also accurately render what colors
 /// will appear as in different lighting environments.
-
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Hct {
     /// hue 0 <= hue < 360; invalid values are corrected.
     hue: f64,
@@ -125,3 +125,52 @@ impl Hct {
         )
     }
 }
+
+impl From<Argb> for Hct {
+    fn from(argb: Argb) -> Hct {
+        Hct::from_int(argb.0)
+    }
+}
+
+impl From<&Hct> for Argb {
+    fn from(hct: &Hct) -> Argb {
+        Argb(hct.to_int())
+    }
+}
+
+impl From<Hct> for Argb {
+    fn from(hct: Hct) -> Argb {
+        Argb(hct.to_int())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Hct {
+    /// Serializes the color as the string of its `Argb`, which unlike hue,
+    /// chroma and tone converts back to the same color.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&Argb::from(self), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Hct {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Argb as serde::Deserialize>::deserialize(deserializer).map(Hct::from)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trips_through_argb() {
+        for argb in [0xff4285f4, 0xffff0000, 0xff000000, 0xffffffff] {
+            let hct = Hct::from_int(argb);
+            let json = serde_json::to_string(&hct).unwrap();
+            assert_eq!(json, format!("\"{}\"", Argb(argb)));
+            assert_eq!(serde_json::from_str::<Hct>(&json).unwrap(), hct);
+        }
+    }
+}
@@ -341,7 +341,7 @@ fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> u32 {
     // ===========================================================
     let viewing_conditions = viewing_conditions::default();
     let t_inner_coeff = 1.0 / (1.64 - 0.29_f64.powf(viewing_conditions.n)).powf(0.73);
-    let e_hue = 0.25 * (hue_radians.cos() + 2.0).cos() + 3.8;
+    let e_hue = 0.25 * ((hue_radians + 2.0).cos() + 3.8);
     let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc * viewing_conditions.ncb;
     let h_sin = hue_radians.sin();
     let h_cos = hue_radians.cos();
@@ -16,6 +16,8 @@ use crate::utils::{
 ///
 /// This struct caches intermediate values of the CAM16 conversion process that
 /// depend only on viewing conditions, enabling speed ups.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ViewingConditions {
     /// Parameters are intermediate values of the CAM16 conversion process. Their
     /// names are shorthand for technical color science terminology, this struct
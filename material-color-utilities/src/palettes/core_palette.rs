@@ -1,20 +1,38 @@
-use crate::hct::Hct;
+use crate::{hct::Hct, utils::color_utils::Argb};
 
 use super::tonal_palette::TonalPalette;
 
 /// Set of colors to generate a `CorePalette` from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct CorePaletteColors {
-    pub primary: u32,
-    pub secondary: Option<u32>,
-    pub tertiary: Option<u32>,
-    pub neutral: Option<u32>,
-    pub neutral_variant: Option<u32>,
-    pub error: Option<u32>,
+    pub primary: Argb,
+    pub secondary: Option<Argb>,
+    pub tertiary: Option<Argb>,
+    pub neutral: Option<Argb>,
+    pub neutral_variant: Option<Argb>,
+    pub error: Option<Argb>,
+}
+
+impl CorePaletteColors {
+    /// Creates a set of colors with only a primary color, from an ARGB
+    /// representation of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - ARGB representation of the primary color
+    pub fn from_int(argb: u32) -> CorePaletteColors {
+        CorePaletteColors {
+            primary: Argb(argb),
+            ..CorePaletteColors::default()
+        }
+    }
 }
 
 /// An intermediate concept between the key color for a UI theme, and a full
 /// color scheme. 5 sets of tones are generated, all except one use the same hue
 /// as the key color, and all vary in chroma.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CorePalette {
     pub a1: TonalPalette,
     pub a2: TonalPalette,
@@ -31,6 +49,15 @@ impl CorePalette {
     ///
     /// * `argb` - ARGB representation of a color
     pub fn of(argb: u32) -> CorePalette {
+        CorePalette::from_argb(Argb(argb))
+    }
+
+    /// Create a `CorePalette` from a color
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color
+    pub fn from_argb(argb: Argb) -> CorePalette {
         CorePalette::new(argb, false)
     }
 
@@ -40,6 +67,15 @@ impl CorePalette {
     ///
     /// * `argb` - ARGB representation of a color
     pub fn content_of(argb: u32) -> CorePalette {
+        CorePalette::content_from_argb(Argb(argb))
+    }
+
+    /// Create a content `CorePalette` from a color
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color
+    pub fn content_from_argb(argb: Argb) -> CorePalette {
         CorePalette::new(argb, true)
     }
 
@@ -86,8 +122,8 @@ impl CorePalette {
         palette
     }
 
-    fn new(argb: u32, is_content: bool) -> CorePalette {
-        let hct = Hct::from_int(argb);
+    fn new(argb: Argb, is_content: bool) -> CorePalette {
+        let hct = Hct::from(argb);
         let hue = hct.hue();
         let chroma = hct.chroma();
         let a1 = TonalPalette::from_hue_and_chroma(hue, if is_content { chroma } else { 48.0 });
@@ -124,3 +160,48 @@ impl CorePalette {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_colors_with_only_primary_matches_of() {
+        let colors = CorePaletteColors::from_int(0xff4285f4);
+        assert_eq!(colors.primary, Argb(0xff4285f4));
+        assert_eq!(
+            CorePalette::from_colors(colors),
+            CorePalette::of(0xff4285f4)
+        );
+        assert_eq!(
+            CorePalette::content_from_colors(colors),
+            CorePalette::content_of(0xff4285f4)
+        );
+    }
+
+    #[test]
+    fn from_colors_overrides_palettes() {
+        let colors = CorePaletteColors {
+            secondary: Some(Argb(0xffff0000)),
+            neutral: Some(Argb(0xff00ff00)),
+            ..CorePaletteColors::from_int(0xff4285f4)
+        };
+        let core = CorePalette::from_colors(colors);
+        assert_eq!(core.a1, CorePalette::of(0xff4285f4).a1);
+        assert_eq!(core.a2, CorePalette::of(0xffff0000).a1);
+        assert_eq!(core.n1, CorePalette::of(0xff00ff00).n1);
+        assert_eq!(core.n2, CorePalette::of(0xff4285f4).n2);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_round_trips() {
+        for core in [
+            CorePalette::of(0xff4285f4),
+            CorePalette::content_of(0xffff0000),
+        ] {
+            let json = serde_json::to_string(&core).unwrap();
+            assert_eq!(serde_json::from_str::<CorePalette>(&json).unwrap(), core);
+        }
+    }
+}
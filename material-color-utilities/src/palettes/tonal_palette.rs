@@ -1,14 +1,36 @@
-use std::{cell::RefCell, collections::HashMap};
+use std::{fmt, sync::OnceLock};
 
-use crate::hct::Hct;
+use crate::{hct::Hct, utils::color_utils::Argb};
 
+/// A convenience class for retrieving colors that are constant in hue and
+/// chroma, but vary in tone.
+///
+/// Palettes compare equal by hue, chroma and key color. Hue and chroma alone
+/// determine the tones, so palettes serialize as `{hue, chroma}`, and
+/// deserializing derives the key color from them like `from_hue_and_chroma`.
+///
+/// Each whole tone is computed once, the first time it is requested, as are
+/// a bounded number of fractional tones. Palettes are `Send + Sync` so they
+/// can be shared across threads.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SerializedPalette", into = "SerializedPalette")
+)]
 pub struct TonalPalette {
-    cache: RefCell<HashMap<u32, u32>>,
+    cache: [OnceLock<u32>; 101],
+    fractional_cache: [OnceLock<(u64, u32)>; FRACTIONAL_CACHE_SIZE],
     hue: f64,
     chroma: f64,
     key_color: Hct,
 }
 
+/// The number of fractional tones a palette remembers. Tones are spread over
+/// the slots by their value to the hundredth; a tone whose slot already holds
+/// another tone is computed every time.
+const FRACTIONAL_CACHE_SIZE: usize = 64;
+
 impl TonalPalette {
     /// Creates a TonalPalette from an ARGB representation of a color.
     ///
@@ -20,8 +42,20 @@ impl TonalPalette {
     ///
     /// Tones matching that color's hue and chroma.
     pub fn from_int(argb: u32) -> TonalPalette {
-        let hct = Hct::from_int(argb);
-        TonalPalette::from_hct(hct)
+        TonalPalette::from_argb(Argb(argb))
+    }
+
+    /// Creates a TonalPalette from a color.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color
+    ///
+    /// # Returns
+    ///
+    /// Tones matching that color's hue and chroma.
+    pub fn from_argb(argb: Argb) -> TonalPalette {
+        TonalPalette::from_hct(Hct::from(argb))
     }
 
     /// Creates a TonalPalette from an Hct.
@@ -34,12 +68,7 @@ impl TonalPalette {
     ///
     /// Tones matching that color's hue and chroma.
     pub fn from_hct(hct: Hct) -> TonalPalette {
-        TonalPalette {
-            cache: RefCell::new(HashMap::new()),
-            hue: hct.hue(),
-            chroma: hct.chroma(),
-            key_color: hct,
-        }
+        TonalPalette::with_key_color(hct.hue(), hct.chroma(), hct)
     }
 
     /// Creates a TonalPalette from a hue and chroma.
@@ -54,8 +83,13 @@ impl TonalPalette {
     /// Tones matching hue and chroma.
     pub fn from_hue_and_chroma(hue: f64, chroma: f64) -> TonalPalette {
         let key_color = TonalPalette::create_key_color(hue, chroma);
+        TonalPalette::with_key_color(hue, chroma, key_color)
+    }
+
+    fn with_key_color(hue: f64, chroma: f64, key_color: Hct) -> TonalPalette {
         TonalPalette {
-            cache: RefCell::new(HashMap::new()),
+            cache: std::array::from_fn(|_| OnceLock::new()),
+            fractional_cache: std::array::from_fn(|_| OnceLock::new()),
             hue,
             chroma,
             key_color,
@@ -91,6 +125,22 @@ impl TonalPalette {
         smallest_delta_hct
     }
 
+    /// The hue of the Material color palette.
+    pub fn hue(&self) -> f64 {
+        self.hue
+    }
+
+    /// The chroma of the Material color palette.
+    pub fn chroma(&self) -> f64 {
+        self.chroma
+    }
+
+    /// The key color is the first tone, starting from T50, that matches the
+    /// palette's chroma.
+    pub fn key_color(&self) -> &Hct {
+        &self.key_color
+    }
+
     /// Returns the ARGB representation of a color with the given tone.
     ///
     /// # Arguments
@@ -103,11 +153,56 @@ impl TonalPalette {
     pub fn tone(&self, tone: u32) -> u32 {
         debug_assert!(tone <= 100);
 
-        *self
-            .cache
-            .borrow_mut()
-            .entry(tone)
-            .or_insert_with(|| Hct::from_hct(self.hue, self.chroma, tone as f64).to_int())
+        let compute = || Hct::from_hct(self.hue, self.chroma, tone as f64).to_int();
+        match self.cache.get(tone as usize) {
+            Some(cell) => *cell.get_or_init(compute),
+            None => compute(),
+        }
+    }
+
+    /// Returns the ARGB representation of a color with the given tone, which
+    /// does not have to be a whole number, ex. 87.5.
+    ///
+    /// Whole tones are cached like `tone`. Other tones share a small cache of
+    /// fixed size, so a palette's memory does not grow with the number of
+    /// tones requested from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - HCT tone, measured from 0 to 100. Tones outside that range
+    ///   are clamped to it, and NaN is treated as 0.
+    ///
+    /// # Returns
+    ///
+    /// ARGB representation of a color with that tone.
+    pub fn fractional_tone(&self, tone: f64) -> u32 {
+        let tone = if tone.is_nan() {
+            0.0
+        } else {
+            tone.clamp(0.0, 100.0)
+        };
+        if tone.fract() == 0.0 {
+            return self.tone(tone as u32);
+        }
+
+        let compute = || Hct::from_hct(self.hue, self.chroma, tone).to_int();
+        let slot = (tone * 100.0).round() as usize % FRACTIONAL_CACHE_SIZE;
+        let (cached_tone, argb) =
+            *self.fractional_cache[slot].get_or_init(|| (tone.to_bits(), compute()));
+        if cached_tone == tone.to_bits() {
+            argb
+        } else {
+            compute()
+        }
+    }
+
+    /// Returns the color with the given tone. See `tone`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - HCT tone, measured from 0 to 100.
+    pub fn argb(&self, tone: u32) -> Argb {
+        Argb(self.tone(tone))
     }
 
     /// Returns the HCT representation of a color with the given tone.
@@ -119,7 +214,162 @@ impl TonalPalette {
     /// # Returns
     ///
     /// HCT representation of a color with that tone.
-    pub fn get_hct(&self, tone: u32) -> Hct {
-        Hct::from_int(self.tone(tone))
+    pub fn get_hct(&self, tone: f64) -> Hct {
+        Hct::from_int(self.fractional_tone(tone))
+    }
+}
+
+impl PartialEq for TonalPalette {
+    fn eq(&self, other: &TonalPalette) -> bool {
+        self.hue == other.hue && self.chroma == other.chroma && self.key_color == other.key_color
+    }
+}
+
+impl fmt::Debug for TonalPalette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TonalPalette")
+            .field("hue", &self.hue)
+            .field("chroma", &self.chroma)
+            .field("key_color", &self.key_color)
+            .finish()
+    }
+}
+
+// Palettes, and the schemes built from them, are shared across threads, so
+// losing `Send`, `Sync` or `Clone`, ex. to a `Cell` in a cache, must not
+// compile.
+const _: fn() = || {
+    fn check<T: Send + Sync + Clone>() {}
+    check::<TonalPalette>();
+    check::<crate::palettes::CorePalette>();
+    check::<crate::scheme::DynamicScheme>();
+};
+
+/// The serialized form of a `TonalPalette`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedPalette {
+    hue: f64,
+    chroma: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedPalette> for TonalPalette {
+    fn from(palette: SerializedPalette) -> TonalPalette {
+        TonalPalette::from_hue_and_chroma(palette.hue, palette.chroma)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TonalPalette> for SerializedPalette {
+    fn from(palette: TonalPalette) -> SerializedPalette {
+        SerializedPalette {
+            hue: palette.hue,
+            chroma: palette.chroma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tones_of_blue() {
+        let blue = TonalPalette::from_int(0xff0000ff);
+        let tones = [
+            (100, 0xffffffff),
+            (95, 0xfff1efff),
+            (90, 0xffe0e0ff),
+            (80, 0xffbec2ff),
+            (70, 0xff9da3ff),
+            (60, 0xff7c84ff),
+            (50, 0xff5a64ff),
+            (40, 0xff343dff),
+            (30, 0xff0000ef),
+            (20, 0xff0001ac),
+            (10, 0xff00006e),
+            (0, 0xff000000),
+        ];
+        for (tone, argb) in tones {
+            assert_eq!(blue.tone(tone), argb, "tone {}", tone);
+        }
+    }
+
+    #[test]
+    fn fractional_tones() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        for tone in 0..=100 {
+            assert_eq!(palette.fractional_tone(tone as f64), palette.tone(tone));
+        }
+        let argb = palette.fractional_tone(87.5);
+        assert_eq!(
+            argb,
+            Hct::from_hct(palette.hue(), palette.chroma(), 87.5).to_int()
+        );
+        assert_ne!(argb, palette.tone(87));
+        assert_ne!(argb, palette.tone(88));
+        assert_eq!(palette.clone().fractional_tone(87.5), argb);
+    }
+
+    #[test]
+    fn fractional_tones_sharing_a_cache_slot() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        let tones = [87.5, 87.5 + FRACTIONAL_CACHE_SIZE as f64 / 100.0, 87.501];
+        for _ in 0..2 {
+            for tone in tones {
+                assert_eq!(
+                    palette.fractional_tone(tone),
+                    Hct::from_hct(palette.hue(), palette.chroma(), tone).to_int(),
+                    "tone {}",
+                    tone
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fractional_tones_are_clamped() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        assert_eq!(palette.fractional_tone(f64::NAN), palette.tone(0));
+        assert_eq!(palette.fractional_tone(-0.5), palette.tone(0));
+        assert_eq!(palette.fractional_tone(f64::INFINITY), palette.tone(100));
+        assert_eq!(palette.get_hct(100.5).to_int(), palette.tone(100));
+    }
+
+    #[test]
+    fn palettes_with_different_key_colors_differ() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        let derived = TonalPalette::from_hue_and_chroma(palette.hue(), palette.chroma());
+        assert_ne!(palette.key_color(), derived.key_color());
+        assert_ne!(palette, derived);
+        assert_eq!(palette, palette.clone());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serializes_hue_and_chroma() {
+        let palette = TonalPalette::from_hue_and_chroma(270.0, 36.0);
+        let json = serde_json::to_string(&palette).unwrap();
+        assert_eq!(json, r#"{"hue":270.0,"chroma":36.0}"#);
+        assert_eq!(
+            serde_json::from_str::<TonalPalette>(&json).unwrap(),
+            palette
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn deserializing_derives_the_key_color() {
+        let palette = TonalPalette::from_int(0xff4285f4);
+        let json = serde_json::to_string(&palette).unwrap();
+        let deserialized: TonalPalette = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized,
+            TonalPalette::from_hue_and_chroma(palette.hue(), palette.chroma())
+        );
+        for tone in 0..=100 {
+            assert_eq!(deserialized.tone(tone), palette.tone(tone));
+        }
     }
 }
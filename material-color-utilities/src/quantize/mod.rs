@@ -0,0 +1,13 @@
+mod point_provider;
+mod point_provider_lab;
+mod quantizer_celebi;
+mod quantizer_map;
+mod quantizer_wsmeans;
+mod quantizer_wu;
+
+pub use point_provider::*;
+pub use point_provider_lab::*;
+pub use quantizer_celebi::*;
+pub use quantizer_map::*;
+pub use quantizer_wsmeans::*;
+pub use quantizer_wu::*;
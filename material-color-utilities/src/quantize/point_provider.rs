@@ -0,0 +1,12 @@
+/// An interface to allow use of different color spaces by quantizers.
+pub trait PointProvider {
+    /// The three components in the color space of an ARGB color.
+    fn point_from_argb(&self, argb: u32) -> [f64; 3];
+
+    /// The ARGB (i.e. hex code) representation of this color.
+    fn argb_from_point(&self, point: &[f64; 3]) -> u32;
+
+    /// Squared distance between two colors. Distance is defined by scientific
+    /// color spaces and referred to as delta E.
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64;
+}
@@ -0,0 +1,33 @@
+use crate::utils::color_utils::{argb_from_lab, lab_from_argb};
+
+use super::point_provider::PointProvider;
+
+/// Provides conversions needed for K-Means quantization. Converting input to
+/// points, and converting the final state of the K-Means algorithm to colors.
+pub struct PointProviderLab;
+
+impl PointProvider for PointProviderLab {
+    /// Convert a color represented in ARGB to a 3-element array of L*a*b*
+    /// coordinates of the color.
+    fn point_from_argb(&self, argb: u32) -> [f64; 3] {
+        lab_from_argb(argb)
+    }
+
+    /// Convert a 3-element array to a color represented in ARGB.
+    fn argb_from_point(&self, point: &[f64; 3]) -> u32 {
+        argb_from_lab(point[0], point[1], point[2])
+    }
+
+    /// Standard CIE 1976 delta E formula also takes the square root, unneeded
+    /// here. This method is used by quantization algorithms to compare distance,
+    /// and the relative ordering is the same, with or without a square root.
+    ///
+    /// This relatively minor optimization is helpful because this method is
+    /// called at least once for each pixel in an image.
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64 {
+        let d_l = one[0] - two[0];
+        let d_a = one[1] - two[1];
+        let d_b = one[2] - two[2];
+        d_l * d_l + d_a * d_a + d_b * d_b
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{quantizer_wsmeans::QuantizerWsmeans, quantizer_wu::QuantizerWu};
+
+/// An image quantizer that improves on the quality of a standard K-Means
+/// algorithm by setting the K-Means initial state to the output of a Wu
+/// quantizer, instead of random centroids. Improves on speed by several
+/// optimizations, as implemented in Wsmeans, or Weighted Square Means, K-Means
+/// with those optimizations.
+///
+/// This algorithm was designed by M. Emre Celebi, and was found in their 2011
+/// paper, Improving the Performance of K-Means for Color Quantization.
+/// https://arxiv.org/abs/1101.0395
+pub struct QuantizerCelebi;
+
+impl QuantizerCelebi {
+    /// Reduces the colors in an image to at most `max_colors` colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - Colors in ARGB format.
+    /// * `max_colors` - The number of colors to divide the image into. A lower
+    ///   number of colors may be returned.
+    ///
+    /// # Returns
+    ///
+    /// A map with keys of colors in ARGB format, and values of the number of
+    /// pixels in the original image that correspond to the color in the
+    /// quantized image.
+    pub fn quantize(pixels: &[u32], max_colors: usize) -> HashMap<u32, u32> {
+        // Distinct boxes can average to the same color. Seed each color once,
+        // in the order Wu created it.
+        let mut seen = HashSet::new();
+        let wu_clusters: Vec<u32> = QuantizerWu::new()
+            .quantize_to_list(pixels, max_colors)
+            .into_iter()
+            .map(|(color, _)| color)
+            .filter(|&color| seen.insert(color))
+            .collect();
+
+        QuantizerWsmeans::quantize(pixels, &wu_clusters, max_colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xffff0000;
+    const GREEN: u32 = 0xff00ff00;
+    const BLUE: u32 = 0xff0000ff;
+
+    fn colors(result: HashMap<u32, u32>) -> Vec<u32> {
+        let mut colors: Vec<u32> = result.into_keys().collect();
+        colors.sort_unstable();
+        colors
+    }
+
+    #[test]
+    fn one_pixel() {
+        assert_eq!(colors(QuantizerCelebi::quantize(&[RED], 128)), vec![RED]);
+        assert_eq!(
+            colors(QuantizerCelebi::quantize(&[GREEN], 128)),
+            vec![GREEN]
+        );
+        assert_eq!(colors(QuantizerCelebi::quantize(&[BLUE], 128)), vec![BLUE]);
+        assert_eq!(
+            colors(QuantizerCelebi::quantize(&[0xff141216], 128)),
+            vec![0xff141216]
+        );
+    }
+
+    #[test]
+    fn repeated_pixels() {
+        assert_eq!(
+            QuantizerCelebi::quantize(&[RED, RED], 128),
+            HashMap::from([(RED, 2)])
+        );
+        assert_eq!(
+            QuantizerCelebi::quantize(&[BLUE; 5], 128),
+            HashMap::from([(BLUE, 5)])
+        );
+    }
+
+    #[test]
+    fn distinct_pixels() {
+        assert_eq!(
+            QuantizerCelebi::quantize(&[RED, RED, GREEN, GREEN, GREEN], 128),
+            HashMap::from([(RED, 2), (GREEN, 3)])
+        );
+        assert_eq!(
+            colors(QuantizerCelebi::quantize(&[RED, GREEN, BLUE], 128)),
+            vec![BLUE, GREEN, RED]
+        );
+    }
+}
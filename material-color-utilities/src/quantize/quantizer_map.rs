@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+/// Creates a dictionary with keys of colors, and values of count of the color
+pub struct QuantizerMap;
+
+impl QuantizerMap {
+    /// Counts the occurrences of each color.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - Colors in ARGB format.
+    ///
+    /// # Returns
+    ///
+    /// A map with keys of colors in ARGB format, and values of the number of
+    /// times that color appears in the input.
+    pub fn quantize(pixels: &[u32]) -> HashMap<u32, u32> {
+        let mut count_by_color = HashMap::new();
+        for &pixel in pixels {
+            *count_by_color.entry(pixel).or_insert(0) += 1;
+        }
+        count_by_color
+    }
+}
@@ -0,0 +1,298 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use super::{point_provider::PointProvider, point_provider_lab::PointProviderLab};
+
+const MAX_ITERATIONS: usize = 10;
+const MIN_MOVEMENT_DISTANCE: f64 = 3.0;
+
+/// Seed used by the reference implementation, so that clustering results
+/// match across platforms.
+const RANDOM_SEED: u64 = 0x42688;
+
+/// The linear congruential generator used by `java.util.Random`. Initial
+/// cluster assignments must draw the same sequence as the reference
+/// implementation for results to agree.
+struct JavaRandom {
+    seed: u64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: u64 = 0x5DEECE66D;
+    const ADDEND: u64 = 0xB;
+    const MASK: u64 = (1 << 48) - 1;
+
+    fn new(seed: u64) -> JavaRandom {
+        JavaRandom {
+            seed: (seed ^ JavaRandom::MULTIPLIER) & JavaRandom::MASK,
+        }
+    }
+
+    fn next(&mut self, bits: u32) -> i32 {
+        self.seed = (self
+            .seed
+            .wrapping_mul(JavaRandom::MULTIPLIER)
+            .wrapping_add(JavaRandom::ADDEND))
+            & JavaRandom::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    fn next_double(&mut self) -> f64 {
+        let high = (self.next(26) as i64) << 27;
+        let low = self.next(27) as i64;
+        (high + low) as f64 * (1.0 / (1_i64 << 53) as f64)
+    }
+
+    fn next_int(&mut self, bound: i32) -> i32 {
+        debug_assert!(bound > 0);
+
+        if bound & -bound == bound {
+            return ((bound as i64 * self.next(31) as i64) >> 31) as i32;
+        }
+
+        loop {
+            let bits = self.next(31);
+            let value = bits % bound;
+            if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 {
+                return value;
+            }
+        }
+    }
+}
+
+/// An image quantizer that improves on the speed of a standard K-Means
+/// algorithm by implementing several optimizations, including deduping
+/// identical pixels and a triangle inequality rule that reduces the number of
+/// comparisons needed to identify which cluster a point should be moved to.
+///
+/// Wsmeans stands for Weighted Square Means.
+///
+/// This algorithm was designed by M. Emre Celebi, and was found in their 2011
+/// paper, Improving the Performance of K-Means for Color Quantization.
+/// https://arxiv.org/abs/1101.0395
+pub struct QuantizerWsmeans;
+
+impl QuantizerWsmeans {
+    /// Reduces the colors in an image to at most `max_colors` colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_pixels` - Colors in ARGB format.
+    /// * `starting_clusters` - Defines the initial state of the quantizer.
+    ///   Passing an empty slice is fine, the implementation will create its own
+    ///   initial state that leads to reproducible results for the same inputs.
+    ///   Passing a slice that is the result of Wu quantization leads to higher
+    ///   quality results.
+    /// * `max_colors` - The number of colors to divide the image into. A lower
+    ///   number of colors may be returned.
+    ///
+    /// # Returns
+    ///
+    /// A map with keys of colors in ARGB format, and values of the number of
+    /// pixels in the original image that correspond to the color in the
+    /// quantized image.
+    pub fn quantize(
+        input_pixels: &[u32],
+        starting_clusters: &[u32],
+        max_colors: usize,
+    ) -> HashMap<u32, u32> {
+        let point_provider = PointProviderLab;
+
+        // Dedupe the input, keeping the pixels in the order they first appear.
+        let mut pixel_to_index: HashMap<u32, usize> = HashMap::new();
+        let mut points: Vec<[f64; 3]> = Vec::new();
+        let mut counts: Vec<u32> = Vec::new();
+        for &input_pixel in input_pixels {
+            match pixel_to_index.get(&input_pixel) {
+                Some(&index) => counts[index] += 1,
+                None => {
+                    pixel_to_index.insert(input_pixel, points.len());
+                    points.push(point_provider.point_from_argb(input_pixel));
+                    counts.push(1);
+                }
+            }
+        }
+        let point_count = points.len();
+
+        let mut cluster_count = max_colors.min(point_count);
+        if !starting_clusters.is_empty() {
+            cluster_count = cluster_count.min(starting_clusters.len());
+        }
+        if cluster_count == 0 {
+            return HashMap::new();
+        }
+
+        let mut random = JavaRandom::new(RANDOM_SEED);
+        let mut clusters: Vec<[f64; 3]> = starting_clusters
+            .iter()
+            .take(cluster_count)
+            .map(|&argb| point_provider.point_from_argb(argb))
+            .collect();
+        while clusters.len() < cluster_count {
+            let l = random.next_double() * 100.0;
+            let a = random.next_double() * (100.0 - (-100.0) + 1.0) + -100.0;
+            let b = random.next_double() * (100.0 - (-100.0) + 1.0) + -100.0;
+            clusters.push([l, a, b]);
+        }
+
+        let mut cluster_indices: Vec<usize> = (0..point_count)
+            .map(|_| random.next_int(cluster_count as i32) as usize)
+            .collect();
+
+        let mut distance_matrix = vec![vec![0.0; cluster_count]; cluster_count];
+
+        let mut pixel_count_sums = vec![0_u32; cluster_count];
+        for iteration in 0..MAX_ITERATIONS {
+            for i in 0..cluster_count {
+                for j in (i + 1)..cluster_count {
+                    let distance = point_provider.distance(&clusters[i], &clusters[j]);
+                    distance_matrix[j][i] = distance;
+                    distance_matrix[i][j] = distance;
+                }
+                // The reference implementation sorts each row in place, and the
+                // triangle inequality check below reads the sorted rows.
+                distance_matrix[i].sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            }
+
+            let mut points_moved = 0;
+            for (point, cluster_index) in points.iter().zip(cluster_indices.iter_mut()) {
+                let previous_cluster_index = *cluster_index;
+                let previous_cluster = &clusters[previous_cluster_index];
+                let previous_distance = point_provider.distance(point, previous_cluster);
+
+                let mut minimum_distance = previous_distance;
+                let mut new_cluster_index = None;
+                for (j, cluster) in clusters.iter().enumerate() {
+                    if distance_matrix[previous_cluster_index][j] >= 4.0 * previous_distance {
+                        continue;
+                    }
+                    let distance = point_provider.distance(point, cluster);
+                    if distance < minimum_distance {
+                        minimum_distance = distance;
+                        new_cluster_index = Some(j);
+                    }
+                }
+                if let Some(new_cluster_index) = new_cluster_index {
+                    let distance_change =
+                        (minimum_distance.sqrt() - previous_distance.sqrt()).abs();
+                    if distance_change > MIN_MOVEMENT_DISTANCE {
+                        points_moved += 1;
+                        *cluster_index = new_cluster_index;
+                    }
+                }
+            }
+
+            if points_moved == 0 && iteration != 0 {
+                break;
+            }
+
+            let mut component_a_sums = vec![0.0; cluster_count];
+            let mut component_b_sums = vec![0.0; cluster_count];
+            let mut component_c_sums = vec![0.0; cluster_count];
+            pixel_count_sums.fill(0);
+            for ((point, &cluster_index), &count) in
+                points.iter().zip(cluster_indices.iter()).zip(counts.iter())
+            {
+                pixel_count_sums[cluster_index] += count;
+                component_a_sums[cluster_index] += point[0] * count as f64;
+                component_b_sums[cluster_index] += point[1] * count as f64;
+                component_c_sums[cluster_index] += point[2] * count as f64;
+            }
+
+            for (i, cluster) in clusters.iter_mut().enumerate() {
+                let count = pixel_count_sums[i];
+                if count == 0 {
+                    *cluster = [0.0, 0.0, 0.0];
+                    continue;
+                }
+                let a = component_a_sums[i] / count as f64;
+                let b = component_b_sums[i] / count as f64;
+                let c = component_c_sums[i] / count as f64;
+                *cluster = [a, b, c];
+            }
+        }
+
+        let mut argb_to_population = HashMap::new();
+        for (cluster, &count) in clusters.iter().zip(pixel_count_sums.iter()) {
+            if count == 0 {
+                continue;
+            }
+
+            let possible_new_cluster = point_provider.argb_from_point(cluster);
+            argb_to_population
+                .entry(possible_new_cluster)
+                .or_insert(count);
+        }
+
+        argb_to_population
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xffff0000;
+    const GREEN: u32 = 0xff00ff00;
+    const BLUE: u32 = 0xff0000ff;
+
+    fn colors(result: HashMap<u32, u32>) -> Vec<u32> {
+        let mut colors: Vec<u32> = result.into_keys().collect();
+        colors.sort_unstable();
+        colors
+    }
+
+    #[test]
+    fn one_pixel() {
+        assert_eq!(
+            colors(QuantizerWsmeans::quantize(&[RED], &[], 128)),
+            vec![RED]
+        );
+        assert_eq!(
+            colors(QuantizerWsmeans::quantize(&[GREEN], &[], 128)),
+            vec![GREEN]
+        );
+        assert_eq!(
+            colors(QuantizerWsmeans::quantize(&[BLUE], &[], 128)),
+            vec![BLUE]
+        );
+        assert_eq!(
+            colors(QuantizerWsmeans::quantize(&[0xff141216], &[], 128)),
+            vec![0xff141216]
+        );
+    }
+
+    #[test]
+    fn repeated_pixels() {
+        assert_eq!(
+            QuantizerWsmeans::quantize(&[RED, RED], &[], 128),
+            HashMap::from([(RED, 2)])
+        );
+        assert_eq!(
+            QuantizerWsmeans::quantize(&[BLUE; 5], &[], 128),
+            HashMap::from([(BLUE, 5)])
+        );
+    }
+
+    #[test]
+    fn distinct_pixels_with_starting_clusters() {
+        assert_eq!(
+            QuantizerWsmeans::quantize(&[RED, RED, GREEN, GREEN, GREEN], &[RED, GREEN], 128),
+            HashMap::from([(RED, 2), (GREEN, 3)])
+        );
+        assert_eq!(
+            colors(QuantizerWsmeans::quantize(
+                &[RED, GREEN, BLUE],
+                &[BLUE, GREEN, RED],
+                128
+            )),
+            vec![BLUE, GREEN, RED]
+        );
+    }
+
+    #[test]
+    fn java_random_matches_reference() {
+        // new java.util.Random(42).nextInt() and .nextDouble()
+        assert_eq!(JavaRandom::new(42).next(32), -1170105035);
+        assert_eq!(JavaRandom::new(42).next_double(), 0.7275636800328681);
+    }
+}
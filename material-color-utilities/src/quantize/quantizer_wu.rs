@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+
+use crate::utils::color_utils::{blue_from_argb, green_from_argb, red_from_argb};
+
+use super::quantizer_map::QuantizerMap;
+
+const INDEX_BITS: u32 = 5;
+const INDEX_COUNT: usize = 33; // ((1 << INDEX_BITS) + 1)
+const TOTAL_SIZE: usize = 35937; // INDEX_COUNT * INDEX_COUNT * INDEX_COUNT
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Red,
+    Green,
+    Blue,
+}
+
+/// Keeps track of the state of each box created as the Wu quantization
+/// algorithm progresses through dividing the image's pixels as plotted in RGB.
+#[derive(Clone, Copy, Default)]
+struct Box {
+    r0: usize,
+    r1: usize,
+    g0: usize,
+    g1: usize,
+    b0: usize,
+    b1: usize,
+    vol: usize,
+}
+
+/// An image quantizer that divides the image's pixels into clusters by
+/// recursively cutting an RGB cube, based on the weight of pixels in each area
+/// of the cube.
+///
+/// The algorithm was described by Xiaolin Wu in Graphic Gems II, published in
+/// 1991.
+pub struct QuantizerWu {
+    weights: Vec<i64>,
+    moments_r: Vec<i64>,
+    moments_g: Vec<i64>,
+    moments_b: Vec<i64>,
+    moments: Vec<f64>,
+    cubes: Vec<Box>,
+}
+
+impl Default for QuantizerWu {
+    fn default() -> Self {
+        QuantizerWu::new()
+    }
+}
+
+impl QuantizerWu {
+    pub fn new() -> QuantizerWu {
+        QuantizerWu {
+            weights: Vec::new(),
+            moments_r: Vec::new(),
+            moments_g: Vec::new(),
+            moments_b: Vec::new(),
+            moments: Vec::new(),
+            cubes: Vec::new(),
+        }
+    }
+
+    /// Reduces the colors in an image to at most `max_colors` colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - Colors in ARGB format.
+    /// * `max_colors` - The number of colors to divide the image into. A lower
+    ///   number of colors may be returned.
+    ///
+    /// # Returns
+    ///
+    /// A map with keys of colors in ARGB format, and values of the number of
+    /// pixels in the original image that belong to that color's cluster.
+    pub fn quantize(&mut self, pixels: &[u32], max_colors: usize) -> HashMap<u32, u32> {
+        let mut color_to_count = HashMap::new();
+        for (color, count) in self.quantize_to_list(pixels, max_colors) {
+            *color_to_count.entry(color).or_insert(0) += count;
+        }
+        color_to_count
+    }
+
+    /// Same as `quantize`, but keeps the clusters in the order the algorithm
+    /// created them, which seeds `QuantizerWsmeans` deterministically.
+    pub(crate) fn quantize_to_list(
+        &mut self,
+        pixels: &[u32],
+        max_colors: usize,
+    ) -> Vec<(u32, u32)> {
+        if max_colors == 0 {
+            return Vec::new();
+        }
+
+        let map_result = QuantizerMap::quantize(pixels);
+        self.construct_histogram(&map_result);
+        self.create_moments();
+        let result_count = self.create_boxes(max_colors);
+        self.create_result(result_count)
+    }
+
+    fn get_index(r: usize, g: usize, b: usize) -> usize {
+        (r << (INDEX_BITS * 2)) + (r << (INDEX_BITS + 1)) + r + (g << INDEX_BITS) + g + b
+    }
+
+    fn construct_histogram(&mut self, pixels: &HashMap<u32, u32>) {
+        self.weights = vec![0; TOTAL_SIZE];
+        self.moments_r = vec![0; TOTAL_SIZE];
+        self.moments_g = vec![0; TOTAL_SIZE];
+        self.moments_b = vec![0; TOTAL_SIZE];
+        self.moments = vec![0.0; TOTAL_SIZE];
+
+        let bits_to_remove = 8 - INDEX_BITS;
+        for (&pixel, &count) in pixels {
+            let red = red_from_argb(pixel) as usize;
+            let green = green_from_argb(pixel) as usize;
+            let blue = blue_from_argb(pixel) as usize;
+            let i_r = (red >> bits_to_remove) + 1;
+            let i_g = (green >> bits_to_remove) + 1;
+            let i_b = (blue >> bits_to_remove) + 1;
+            let index = QuantizerWu::get_index(i_r, i_g, i_b);
+            let count = count as i64;
+            self.weights[index] += count;
+            self.moments_r[index] += red as i64 * count;
+            self.moments_g[index] += green as i64 * count;
+            self.moments_b[index] += blue as i64 * count;
+            self.moments[index] += count as f64 * (red * red + green * green + blue * blue) as f64;
+        }
+    }
+
+    fn create_moments(&mut self) {
+        for r in 1..INDEX_COUNT {
+            let mut area = [0_i64; INDEX_COUNT];
+            let mut area_r = [0_i64; INDEX_COUNT];
+            let mut area_g = [0_i64; INDEX_COUNT];
+            let mut area_b = [0_i64; INDEX_COUNT];
+            let mut area2 = [0.0_f64; INDEX_COUNT];
+
+            for g in 1..INDEX_COUNT {
+                let mut line = 0_i64;
+                let mut line_r = 0_i64;
+                let mut line_g = 0_i64;
+                let mut line_b = 0_i64;
+                let mut line2 = 0.0_f64;
+
+                for b in 1..INDEX_COUNT {
+                    let index = QuantizerWu::get_index(r, g, b);
+                    line += self.weights[index];
+                    line_r += self.moments_r[index];
+                    line_g += self.moments_g[index];
+                    line_b += self.moments_b[index];
+                    line2 += self.moments[index];
+
+                    area[b] += line;
+                    area_r[b] += line_r;
+                    area_g[b] += line_g;
+                    area_b[b] += line_b;
+                    area2[b] += line2;
+
+                    let previous_index = QuantizerWu::get_index(r - 1, g, b);
+                    self.weights[index] = self.weights[previous_index] + area[b];
+                    self.moments_r[index] = self.moments_r[previous_index] + area_r[b];
+                    self.moments_g[index] = self.moments_g[previous_index] + area_g[b];
+                    self.moments_b[index] = self.moments_b[previous_index] + area_b[b];
+                    self.moments[index] = self.moments[previous_index] + area2[b];
+                }
+            }
+        }
+    }
+
+    /// Divides the RGB cube into at most `max_color_count` boxes, returning the
+    /// number of boxes actually generated.
+    fn create_boxes(&mut self, max_color_count: usize) -> usize {
+        self.cubes = vec![Box::default(); max_color_count];
+        let mut volume_variance = vec![0.0; max_color_count];
+        self.cubes[0].r1 = INDEX_COUNT - 1;
+        self.cubes[0].g1 = INDEX_COUNT - 1;
+        self.cubes[0].b1 = INDEX_COUNT - 1;
+
+        let mut generated_color_count = max_color_count;
+        let mut next = 0;
+        let mut i = 1;
+        while i < max_color_count {
+            let (mut one, mut two) = (self.cubes[next], self.cubes[i]);
+            if self.cut(&mut one, &mut two) {
+                self.cubes[next] = one;
+                self.cubes[i] = two;
+                volume_variance[next] = if one.vol > 1 {
+                    self.variance(&one)
+                } else {
+                    0.0
+                };
+                volume_variance[i] = if two.vol > 1 {
+                    self.variance(&two)
+                } else {
+                    0.0
+                };
+            } else {
+                volume_variance[next] = 0.0;
+                // The cut failed, so this box index is retried.
+                i -= 1;
+            }
+
+            next = 0;
+            let mut temp = volume_variance[0];
+            for (j, &variance) in volume_variance.iter().enumerate().take(i + 1).skip(1) {
+                if variance > temp {
+                    temp = variance;
+                    next = j;
+                }
+            }
+            if temp <= 0.0 {
+                generated_color_count = i + 1;
+                break;
+            }
+
+            i += 1;
+        }
+
+        generated_color_count
+    }
+
+    fn create_result(&self, color_count: usize) -> Vec<(u32, u32)> {
+        let mut colors = Vec::new();
+        for cube in self.cubes.iter().take(color_count) {
+            let weight = QuantizerWu::volume(cube, &self.weights);
+            if weight > 0 {
+                let r = (QuantizerWu::volume(cube, &self.moments_r) / weight) as u32;
+                let g = (QuantizerWu::volume(cube, &self.moments_g) / weight) as u32;
+                let b = (QuantizerWu::volume(cube, &self.moments_b) / weight) as u32;
+                let color = (255 << 24) | ((r & 0x0ff) << 16) | ((g & 0x0ff) << 8) | (b & 0x0ff);
+                colors.push((color, weight as u32));
+            }
+        }
+        colors
+    }
+
+    fn variance(&self, cube: &Box) -> f64 {
+        let dr = QuantizerWu::volume(cube, &self.moments_r) as f64;
+        let dg = QuantizerWu::volume(cube, &self.moments_g) as f64;
+        let db = QuantizerWu::volume(cube, &self.moments_b) as f64;
+        let moments = &self.moments;
+        let xx = moments[QuantizerWu::get_index(cube.r1, cube.g1, cube.b1)]
+            - moments[QuantizerWu::get_index(cube.r1, cube.g1, cube.b0)]
+            - moments[QuantizerWu::get_index(cube.r1, cube.g0, cube.b1)]
+            + moments[QuantizerWu::get_index(cube.r1, cube.g0, cube.b0)]
+            - moments[QuantizerWu::get_index(cube.r0, cube.g1, cube.b1)]
+            + moments[QuantizerWu::get_index(cube.r0, cube.g1, cube.b0)]
+            + moments[QuantizerWu::get_index(cube.r0, cube.g0, cube.b1)]
+            - moments[QuantizerWu::get_index(cube.r0, cube.g0, cube.b0)];
+
+        let hypotenuse = dr * dr + dg * dg + db * db;
+        let volume = QuantizerWu::volume(cube, &self.weights) as f64;
+        xx - hypotenuse / volume
+    }
+
+    fn cut(&self, one: &mut Box, two: &mut Box) -> bool {
+        let whole_r = QuantizerWu::volume(one, &self.moments_r);
+        let whole_g = QuantizerWu::volume(one, &self.moments_g);
+        let whole_b = QuantizerWu::volume(one, &self.moments_b);
+        let whole_w = QuantizerWu::volume(one, &self.weights);
+        let whole = [whole_r, whole_g, whole_b, whole_w];
+
+        let (cut_r, max_r) = self.maximize(one, Direction::Red, one.r0 + 1, one.r1, &whole);
+        let (cut_g, max_g) = self.maximize(one, Direction::Green, one.g0 + 1, one.g1, &whole);
+        let (cut_b, max_b) = self.maximize(one, Direction::Blue, one.b0 + 1, one.b1, &whole);
+
+        let cut_direction = if max_r >= max_g && max_r >= max_b {
+            if cut_r.is_none() {
+                return false;
+            }
+            Direction::Red
+        } else if max_g >= max_r && max_g >= max_b {
+            Direction::Green
+        } else {
+            Direction::Blue
+        };
+
+        two.r1 = one.r1;
+        two.g1 = one.g1;
+        two.b1 = one.b1;
+
+        match cut_direction {
+            Direction::Red => {
+                one.r1 = cut_r.unwrap_or(one.r1);
+                two.r0 = one.r1;
+                two.g0 = one.g0;
+                two.b0 = one.b0;
+            }
+            Direction::Green => {
+                one.g1 = cut_g.unwrap_or(one.g1);
+                two.r0 = one.r0;
+                two.g0 = one.g1;
+                two.b0 = one.b0;
+            }
+            Direction::Blue => {
+                one.b1 = cut_b.unwrap_or(one.b1);
+                two.r0 = one.r0;
+                two.g0 = one.g0;
+                two.b0 = one.b1;
+            }
+        }
+
+        one.vol = (one.r1 - one.r0) * (one.g1 - one.g0) * (one.b1 - one.b0);
+        two.vol = (two.r1 - two.r0) * (two.g1 - two.g0) * (two.b1 - two.b0);
+
+        true
+    }
+
+    /// Finds the position along `direction` that best splits `cube`, returning
+    /// the cut location (if any) and the resulting variance measure.
+    fn maximize(
+        &self,
+        cube: &Box,
+        direction: Direction,
+        first: usize,
+        last: usize,
+        whole: &[i64; 4],
+    ) -> (Option<usize>, f64) {
+        let bottom_r = QuantizerWu::bottom(cube, direction, &self.moments_r);
+        let bottom_g = QuantizerWu::bottom(cube, direction, &self.moments_g);
+        let bottom_b = QuantizerWu::bottom(cube, direction, &self.moments_b);
+        let bottom_w = QuantizerWu::bottom(cube, direction, &self.weights);
+
+        let mut max = 0.0;
+        let mut cut = None;
+
+        for i in first..last {
+            let mut half_r = bottom_r + QuantizerWu::top(cube, direction, i, &self.moments_r);
+            let mut half_g = bottom_g + QuantizerWu::top(cube, direction, i, &self.moments_g);
+            let mut half_b = bottom_b + QuantizerWu::top(cube, direction, i, &self.moments_b);
+            let mut half_w = bottom_w + QuantizerWu::top(cube, direction, i, &self.weights);
+            if half_w == 0 {
+                continue;
+            }
+
+            let mut temp_numerator = (half_r * half_r + half_g * half_g + half_b * half_b) as f64;
+            let mut temp_denominator = half_w as f64;
+            let mut temp = temp_numerator / temp_denominator;
+
+            half_r = whole[0] - half_r;
+            half_g = whole[1] - half_g;
+            half_b = whole[2] - half_b;
+            half_w = whole[3] - half_w;
+            if half_w == 0 {
+                continue;
+            }
+
+            temp_numerator = (half_r * half_r + half_g * half_g + half_b * half_b) as f64;
+            temp_denominator = half_w as f64;
+            temp += temp_numerator / temp_denominator;
+
+            if temp > max {
+                max = temp;
+                cut = Some(i);
+            }
+        }
+
+        (cut, max)
+    }
+
+    fn volume(cube: &Box, moment: &[i64]) -> i64 {
+        moment[QuantizerWu::get_index(cube.r1, cube.g1, cube.b1)]
+            - moment[QuantizerWu::get_index(cube.r1, cube.g1, cube.b0)]
+            - moment[QuantizerWu::get_index(cube.r1, cube.g0, cube.b1)]
+            + moment[QuantizerWu::get_index(cube.r1, cube.g0, cube.b0)]
+            - moment[QuantizerWu::get_index(cube.r0, cube.g1, cube.b1)]
+            + moment[QuantizerWu::get_index(cube.r0, cube.g1, cube.b0)]
+            + moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b1)]
+            - moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b0)]
+    }
+
+    fn bottom(cube: &Box, direction: Direction, moment: &[i64]) -> i64 {
+        match direction {
+            Direction::Red => {
+                -moment[QuantizerWu::get_index(cube.r0, cube.g1, cube.b1)]
+                    + moment[QuantizerWu::get_index(cube.r0, cube.g1, cube.b0)]
+                    + moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b1)]
+                    - moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b0)]
+            }
+            Direction::Green => {
+                -moment[QuantizerWu::get_index(cube.r1, cube.g0, cube.b1)]
+                    + moment[QuantizerWu::get_index(cube.r1, cube.g0, cube.b0)]
+                    + moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b1)]
+                    - moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b0)]
+            }
+            Direction::Blue => {
+                -moment[QuantizerWu::get_index(cube.r1, cube.g1, cube.b0)]
+                    + moment[QuantizerWu::get_index(cube.r1, cube.g0, cube.b0)]
+                    + moment[QuantizerWu::get_index(cube.r0, cube.g1, cube.b0)]
+                    - moment[QuantizerWu::get_index(cube.r0, cube.g0, cube.b0)]
+            }
+        }
+    }
+
+    fn top(cube: &Box, direction: Direction, position: usize, moment: &[i64]) -> i64 {
+        match direction {
+            Direction::Red => {
+                moment[QuantizerWu::get_index(position, cube.g1, cube.b1)]
+                    - moment[QuantizerWu::get_index(position, cube.g1, cube.b0)]
+                    - moment[QuantizerWu::get_index(position, cube.g0, cube.b1)]
+                    + moment[QuantizerWu::get_index(position, cube.g0, cube.b0)]
+            }
+            Direction::Green => {
+                moment[QuantizerWu::get_index(cube.r1, position, cube.b1)]
+                    - moment[QuantizerWu::get_index(cube.r1, position, cube.b0)]
+                    - moment[QuantizerWu::get_index(cube.r0, position, cube.b1)]
+                    + moment[QuantizerWu::get_index(cube.r0, position, cube.b0)]
+            }
+            Direction::Blue => {
+                moment[QuantizerWu::get_index(cube.r1, cube.g1, position)]
+                    - moment[QuantizerWu::get_index(cube.r1, cube.g0, position)]
+                    - moment[QuantizerWu::get_index(cube.r0, cube.g1, position)]
+                    + moment[QuantizerWu::get_index(cube.r0, cube.g0, position)]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xffff0000;
+    const GREEN: u32 = 0xff00ff00;
+    const BLUE: u32 = 0xff0000ff;
+
+    fn colors(result: HashMap<u32, u32>) -> Vec<u32> {
+        let mut colors: Vec<u32> = result.into_keys().collect();
+        colors.sort_unstable();
+        colors
+    }
+
+    #[test]
+    fn one_pixel() {
+        assert_eq!(colors(QuantizerWu::new().quantize(&[RED], 128)), vec![RED]);
+        assert_eq!(
+            colors(QuantizerWu::new().quantize(&[GREEN], 128)),
+            vec![GREEN]
+        );
+        assert_eq!(
+            colors(QuantizerWu::new().quantize(&[BLUE], 128)),
+            vec![BLUE]
+        );
+        assert_eq!(
+            colors(QuantizerWu::new().quantize(&[0xff141216], 128)),
+            vec![0xff141216]
+        );
+    }
+
+    #[test]
+    fn repeated_pixels() {
+        assert_eq!(
+            QuantizerWu::new().quantize(&[RED, RED], 128),
+            HashMap::from([(RED, 2)])
+        );
+        assert_eq!(
+            QuantizerWu::new().quantize(&[BLUE; 5], 128),
+            HashMap::from([(BLUE, 5)])
+        );
+    }
+
+    #[test]
+    fn distinct_pixels() {
+        assert_eq!(
+            QuantizerWu::new().quantize(&[RED, RED, GREEN, GREEN, GREEN], 128),
+            HashMap::from([(RED, 2), (GREEN, 3)])
+        );
+        assert_eq!(
+            colors(QuantizerWu::new().quantize(&[RED, GREEN, BLUE], 128)),
+            vec![BLUE, GREEN, RED]
+        );
+    }
+}
@@ -0,0 +1,551 @@
+use crate::{
+    contrast::ContrastAlgorithm,
+    dislike::DislikeAnalyzer,
+    dynamiccolor::MaterialDynamicColors,
+    hct::Hct,
+    palettes::{CorePalette, TonalPalette},
+    temperature::TemperatureCache,
+    utils::math_utils::sanitize_degrees_double,
+};
+
+use super::variant::Variant;
+
+const VIBRANT_HUES: [f64; 9] = [0.0, 41.0, 61.0, 101.0, 131.0, 181.0, 251.0, 301.0, 360.0];
+const VIBRANT_SECONDARY_ROTATIONS: [f64; 9] =
+    [18.0, 15.0, 10.0, 12.0, 15.0, 18.0, 15.0, 12.0, 12.0];
+const VIBRANT_TERTIARY_ROTATIONS: [f64; 9] = [35.0, 30.0, 20.0, 25.0, 30.0, 35.0, 30.0, 25.0, 25.0];
+
+const EXPRESSIVE_HUES: [f64; 9] = [0.0, 21.0, 51.0, 121.0, 151.0, 191.0, 271.0, 321.0, 360.0];
+const EXPRESSIVE_SECONDARY_ROTATIONS: [f64; 9] =
+    [45.0, 95.0, 45.0, 20.0, 45.0, 90.0, 45.0, 45.0, 45.0];
+const EXPRESSIVE_TERTIARY_ROTATIONS: [f64; 9] =
+    [120.0, 120.0, 20.0, 45.0, 20.0, 15.0, 20.0, 120.0, 120.0];
+
+/// Provides important settings for creating colors dynamically, and 6 color
+/// palettes. Requires: 1. A color. (source color) 2. A theme. (Variant) 3.
+/// Whether or not its dark mode. 4. Contrast level. (-1 to 1, currently
+/// contrast ratio 3.0 and 7.0)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicScheme {
+    /// The source color of the theme as an ARGB integer.
+    pub source_color_argb: u32,
+
+    /// The source color of the theme in HCT.
+    pub source_color_hct: Hct,
+
+    /// The variant, or style, of the theme.
+    pub variant: Variant,
+
+    /// Whether or not the scheme is in 'dark mode' or 'light mode'.
+    pub is_dark: bool,
+
+    /// Value from -1 to 1. -1 represents minimum contrast, 0 represents
+    /// standard (i.e. the design as spec'd), and 1 represents maximum contrast.
+    pub contrast_level: f64,
+
+    /// How contrast between foreground and background roles is measured.
+    /// Defaults to WCAG 2 contrast ratios.
+    pub contrast_algorithm: ContrastAlgorithm,
+
+    /// Given a tone, produces a color. Hue and chroma of the color are
+    /// specified in the design specification of the variant. Usually colorful.
+    pub primary_palette: TonalPalette,
+
+    /// Given a tone, produces a color. Hue and chroma of the color are
+    /// specified in the design specification of the variant. Usually less
+    /// colorful.
+    pub secondary_palette: TonalPalette,
+
+    /// Given a tone, produces a color. Hue and chroma of the color are
+    /// specified in the design specification of the variant. Usually a
+    /// different hue from primary and colorful.
+    pub tertiary_palette: TonalPalette,
+
+    /// Given a tone, produces a color. Hue and chroma of the color are
+    /// specified in the design specification of the variant. Usually not
+    /// colorful at all, intended for background & surface colors.
+    pub neutral_palette: TonalPalette,
+
+    /// Given a tone, produces a color. Hue and chroma of the color are
+    /// specified in the design specification of the variant. Usually not
+    /// colorful, but slightly more colorful than Neutral. Intended for
+    /// backgrounds & surfaces.
+    pub neutral_variant_palette: TonalPalette,
+
+    /// Given a tone, produces a reddish, colorful, color.
+    pub error_palette: TonalPalette,
+}
+
+impl DynamicScheme {
+    /// Creates the palettes of a scheme the way the reference implementation
+    /// does for each `Variant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_color_hct` - The source color of the theme.
+    /// * `variant` - The variant, or style, of the theme.
+    /// * `is_dark` - Whether the scheme is in dark mode or light mode.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    pub fn new(
+        source_color_hct: Hct,
+        variant: Variant,
+        is_dark: bool,
+        contrast_level: f64,
+    ) -> DynamicScheme {
+        let hue = source_color_hct.hue();
+        let chroma = source_color_hct.chroma();
+        let (primary, secondary, tertiary, neutral, neutral_variant) = match variant {
+            Variant::Monochrome => (
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+            ),
+            Variant::Neutral => (
+                TonalPalette::from_hue_and_chroma(hue, 12.0),
+                TonalPalette::from_hue_and_chroma(hue, 8.0),
+                TonalPalette::from_hue_and_chroma(hue, 16.0),
+                TonalPalette::from_hue_and_chroma(hue, 2.0),
+                TonalPalette::from_hue_and_chroma(hue, 2.0),
+            ),
+            Variant::TonalSpot => (
+                TonalPalette::from_hue_and_chroma(hue, 36.0),
+                TonalPalette::from_hue_and_chroma(hue, 16.0),
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue + 60.0), 24.0),
+                TonalPalette::from_hue_and_chroma(hue, 6.0),
+                TonalPalette::from_hue_and_chroma(hue, 8.0),
+            ),
+            Variant::Vibrant => (
+                TonalPalette::from_hue_and_chroma(hue, 200.0),
+                TonalPalette::from_hue_and_chroma(
+                    DynamicScheme::get_rotated_hue(
+                        &source_color_hct,
+                        &VIBRANT_HUES,
+                        &VIBRANT_SECONDARY_ROTATIONS,
+                    ),
+                    24.0,
+                ),
+                TonalPalette::from_hue_and_chroma(
+                    DynamicScheme::get_rotated_hue(
+                        &source_color_hct,
+                        &VIBRANT_HUES,
+                        &VIBRANT_TERTIARY_ROTATIONS,
+                    ),
+                    32.0,
+                ),
+                TonalPalette::from_hue_and_chroma(hue, 10.0),
+                TonalPalette::from_hue_and_chroma(hue, 12.0),
+            ),
+            Variant::Expressive => (
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue + 240.0), 40.0),
+                TonalPalette::from_hue_and_chroma(
+                    DynamicScheme::get_rotated_hue(
+                        &source_color_hct,
+                        &EXPRESSIVE_HUES,
+                        &EXPRESSIVE_SECONDARY_ROTATIONS,
+                    ),
+                    24.0,
+                ),
+                TonalPalette::from_hue_and_chroma(
+                    DynamicScheme::get_rotated_hue(
+                        &source_color_hct,
+                        &EXPRESSIVE_HUES,
+                        &EXPRESSIVE_TERTIARY_ROTATIONS,
+                    ),
+                    32.0,
+                ),
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue + 15.0), 8.0),
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue + 15.0), 12.0),
+            ),
+            Variant::Fidelity => (
+                TonalPalette::from_hue_and_chroma(hue, chroma),
+                TonalPalette::from_hue_and_chroma(hue, (chroma - 32.0).max(chroma * 0.5)),
+                TonalPalette::from_hct(DislikeAnalyzer::fix_if_disliked(
+                    TemperatureCache::new(&source_color_hct).complement(),
+                )),
+                TonalPalette::from_hue_and_chroma(hue, chroma / 8.0),
+                TonalPalette::from_hue_and_chroma(hue, chroma / 8.0 + 4.0),
+            ),
+            Variant::Content => (
+                TonalPalette::from_hue_and_chroma(hue, chroma),
+                TonalPalette::from_hue_and_chroma(hue, (chroma - 32.0).max(chroma * 0.5)),
+                TonalPalette::from_hct(DislikeAnalyzer::fix_if_disliked(
+                    TemperatureCache::new(&source_color_hct)
+                        .analogous(3, 6)
+                        .swap_remove(2),
+                )),
+                TonalPalette::from_hue_and_chroma(hue, chroma / 8.0),
+                TonalPalette::from_hue_and_chroma(hue, chroma / 8.0 + 4.0),
+            ),
+            Variant::Rainbow => (
+                TonalPalette::from_hue_and_chroma(hue, 48.0),
+                TonalPalette::from_hue_and_chroma(hue, 16.0),
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue + 60.0), 24.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+                TonalPalette::from_hue_and_chroma(hue, 0.0),
+            ),
+            Variant::FruitSalad => (
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue - 50.0), 48.0),
+                TonalPalette::from_hue_and_chroma(sanitize_degrees_double(hue - 50.0), 36.0),
+                TonalPalette::from_hue_and_chroma(hue, 36.0),
+                TonalPalette::from_hue_and_chroma(hue, 10.0),
+                TonalPalette::from_hue_and_chroma(hue, 16.0),
+            ),
+        };
+
+        DynamicScheme {
+            source_color_argb: source_color_hct.to_int(),
+            source_color_hct,
+            variant,
+            is_dark,
+            contrast_level,
+            contrast_algorithm: ContrastAlgorithm::default(),
+            primary_palette: primary,
+            secondary_palette: secondary,
+            tertiary_palette: tertiary,
+            neutral_palette: neutral,
+            neutral_variant_palette: neutral_variant,
+            error_palette: TonalPalette::from_hue_and_chroma(25.0, 84.0),
+        }
+    }
+
+    /// Creates a scheme whose roles are resolved against the palettes of a
+    /// `CorePalette`, rather than palettes derived from a `Variant`.
+    ///
+    /// The primary palette's key color is used as the source color, and the
+    /// scheme is treated as `Variant::TonalSpot`, so every role uses its
+    /// standard tone at a contrast level of 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `core` - The palettes to resolve roles against.
+    /// * `is_dark` - Whether the scheme is in dark mode or light mode.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    pub fn from_core_palette(
+        core: &CorePalette,
+        is_dark: bool,
+        contrast_level: f64,
+    ) -> DynamicScheme {
+        let source_color_hct = *core.a1.key_color();
+
+        DynamicScheme {
+            source_color_argb: source_color_hct.to_int(),
+            source_color_hct,
+            variant: Variant::TonalSpot,
+            is_dark,
+            contrast_level,
+            contrast_algorithm: ContrastAlgorithm::default(),
+            primary_palette: core.a1.clone(),
+            secondary_palette: core.a2.clone(),
+            tertiary_palette: core.a3.clone(),
+            neutral_palette: core.n1.clone(),
+            neutral_variant_palette: core.n2.clone(),
+            error_palette: core.error.clone(),
+        }
+    }
+
+    /// Given a set of hues and set of hue rotations, locate which hues the
+    /// source color's hue is between, apply the rotation at the same index as
+    /// the first hue in the range, and return the rotated hue.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_color` - The color whose hue should be rotated.
+    /// * `hues` - A set of hues.
+    /// * `rotations` - A set of hue rotations.
+    ///
+    /// # Returns
+    ///
+    /// Color's hue with a rotation applied.
+    pub fn get_rotated_hue(source_color: &Hct, hues: &[f64], rotations: &[f64]) -> f64 {
+        let source_hue = source_color.hue();
+        if rotations.len() == 1 {
+            return sanitize_degrees_double(source_hue + rotations[0]);
+        }
+        for (i, pair) in hues.windows(2).enumerate() {
+            let this_hue = pair[0];
+            let next_hue = pair[1];
+            if this_hue < source_hue && source_hue < next_hue {
+                return sanitize_degrees_double(source_hue + rotations[i]);
+            }
+        }
+        // If this statement executes, something is wrong, there should have been
+        // a rotation found using the arrays.
+        source_hue
+    }
+
+    /// The `primary_palette_key_color` role of this scheme as an ARGB integer.
+    pub fn primary_palette_key_color(&self) -> u32 {
+        MaterialDynamicColors::primary_palette_key_color().get_argb(self)
+    }
+
+    /// The `secondary_palette_key_color` role of this scheme as an ARGB integer.
+    pub fn secondary_palette_key_color(&self) -> u32 {
+        MaterialDynamicColors::secondary_palette_key_color().get_argb(self)
+    }
+
+    /// The `tertiary_palette_key_color` role of this scheme as an ARGB integer.
+    pub fn tertiary_palette_key_color(&self) -> u32 {
+        MaterialDynamicColors::tertiary_palette_key_color().get_argb(self)
+    }
+
+    /// The `neutral_palette_key_color` role of this scheme as an ARGB integer.
+    pub fn neutral_palette_key_color(&self) -> u32 {
+        MaterialDynamicColors::neutral_palette_key_color().get_argb(self)
+    }
+
+    /// The `neutral_variant_palette_key_color` role of this scheme as an ARGB integer.
+    pub fn neutral_variant_palette_key_color(&self) -> u32 {
+        MaterialDynamicColors::neutral_variant_palette_key_color().get_argb(self)
+    }
+
+    /// The `background` role of this scheme as an ARGB integer.
+    pub fn background(&self) -> u32 {
+        MaterialDynamicColors::background().get_argb(self)
+    }
+
+    /// The `on_background` role of this scheme as an ARGB integer.
+    pub fn on_background(&self) -> u32 {
+        MaterialDynamicColors::on_background().get_argb(self)
+    }
+
+    /// The `surface` role of this scheme as an ARGB integer.
+    pub fn surface(&self) -> u32 {
+        MaterialDynamicColors::surface().get_argb(self)
+    }
+
+    /// The `surface_dim` role of this scheme as an ARGB integer.
+    pub fn surface_dim(&self) -> u32 {
+        MaterialDynamicColors::surface_dim().get_argb(self)
+    }
+
+    /// The `surface_bright` role of this scheme as an ARGB integer.
+    pub fn surface_bright(&self) -> u32 {
+        MaterialDynamicColors::surface_bright().get_argb(self)
+    }
+
+    /// The `surface_container_lowest` role of this scheme as an ARGB integer.
+    pub fn surface_container_lowest(&self) -> u32 {
+        MaterialDynamicColors::surface_container_lowest().get_argb(self)
+    }
+
+    /// The `surface_container_low` role of this scheme as an ARGB integer.
+    pub fn surface_container_low(&self) -> u32 {
+        MaterialDynamicColors::surface_container_low().get_argb(self)
+    }
+
+    /// The `surface_container` role of this scheme as an ARGB integer.
+    pub fn surface_container(&self) -> u32 {
+        MaterialDynamicColors::surface_container().get_argb(self)
+    }
+
+    /// The `surface_container_high` role of this scheme as an ARGB integer.
+    pub fn surface_container_high(&self) -> u32 {
+        MaterialDynamicColors::surface_container_high().get_argb(self)
+    }
+
+    /// The `surface_container_highest` role of this scheme as an ARGB integer.
+    pub fn surface_container_highest(&self) -> u32 {
+        MaterialDynamicColors::surface_container_highest().get_argb(self)
+    }
+
+    /// The `on_surface` role of this scheme as an ARGB integer.
+    pub fn on_surface(&self) -> u32 {
+        MaterialDynamicColors::on_surface().get_argb(self)
+    }
+
+    /// The `surface_variant` role of this scheme as an ARGB integer.
+    pub fn surface_variant(&self) -> u32 {
+        MaterialDynamicColors::surface_variant().get_argb(self)
+    }
+
+    /// The `on_surface_variant` role of this scheme as an ARGB integer.
+    pub fn on_surface_variant(&self) -> u32 {
+        MaterialDynamicColors::on_surface_variant().get_argb(self)
+    }
+
+    /// The `inverse_surface` role of this scheme as an ARGB integer.
+    pub fn inverse_surface(&self) -> u32 {
+        MaterialDynamicColors::inverse_surface().get_argb(self)
+    }
+
+    /// The `inverse_on_surface` role of this scheme as an ARGB integer.
+    pub fn inverse_on_surface(&self) -> u32 {
+        MaterialDynamicColors::inverse_on_surface().get_argb(self)
+    }
+
+    /// The `outline` role of this scheme as an ARGB integer.
+    pub fn outline(&self) -> u32 {
+        MaterialDynamicColors::outline().get_argb(self)
+    }
+
+    /// The `outline_variant` role of this scheme as an ARGB integer.
+    pub fn outline_variant(&self) -> u32 {
+        MaterialDynamicColors::outline_variant().get_argb(self)
+    }
+
+    /// The `shadow` role of this scheme as an ARGB integer.
+    pub fn shadow(&self) -> u32 {
+        MaterialDynamicColors::shadow().get_argb(self)
+    }
+
+    /// The `scrim` role of this scheme as an ARGB integer.
+    pub fn scrim(&self) -> u32 {
+        MaterialDynamicColors::scrim().get_argb(self)
+    }
+
+    /// The `surface_tint` role of this scheme as an ARGB integer.
+    pub fn surface_tint(&self) -> u32 {
+        MaterialDynamicColors::surface_tint().get_argb(self)
+    }
+
+    /// The `primary` role of this scheme as an ARGB integer.
+    pub fn primary(&self) -> u32 {
+        MaterialDynamicColors::primary().get_argb(self)
+    }
+
+    /// The `on_primary` role of this scheme as an ARGB integer.
+    pub fn on_primary(&self) -> u32 {
+        MaterialDynamicColors::on_primary().get_argb(self)
+    }
+
+    /// The `primary_container` role of this scheme as an ARGB integer.
+    pub fn primary_container(&self) -> u32 {
+        MaterialDynamicColors::primary_container().get_argb(self)
+    }
+
+    /// The `on_primary_container` role of this scheme as an ARGB integer.
+    pub fn on_primary_container(&self) -> u32 {
+        MaterialDynamicColors::on_primary_container().get_argb(self)
+    }
+
+    /// The `inverse_primary` role of this scheme as an ARGB integer.
+    pub fn inverse_primary(&self) -> u32 {
+        MaterialDynamicColors::inverse_primary().get_argb(self)
+    }
+
+    /// The `secondary` role of this scheme as an ARGB integer.
+    pub fn secondary(&self) -> u32 {
+        MaterialDynamicColors::secondary().get_argb(self)
+    }
+
+    /// The `on_secondary` role of this scheme as an ARGB integer.
+    pub fn on_secondary(&self) -> u32 {
+        MaterialDynamicColors::on_secondary().get_argb(self)
+    }
+
+    /// The `secondary_container` role of this scheme as an ARGB integer.
+    pub fn secondary_container(&self) -> u32 {
+        MaterialDynamicColors::secondary_container().get_argb(self)
+    }
+
+    /// The `on_secondary_container` role of this scheme as an ARGB integer.
+    pub fn on_secondary_container(&self) -> u32 {
+        MaterialDynamicColors::on_secondary_container().get_argb(self)
+    }
+
+    /// The `tertiary` role of this scheme as an ARGB integer.
+    pub fn tertiary(&self) -> u32 {
+        MaterialDynamicColors::tertiary().get_argb(self)
+    }
+
+    /// The `on_tertiary` role of this scheme as an ARGB integer.
+    pub fn on_tertiary(&self) -> u32 {
+        MaterialDynamicColors::on_tertiary().get_argb(self)
+    }
+
+    /// The `tertiary_container` role of this scheme as an ARGB integer.
+    pub fn tertiary_container(&self) -> u32 {
+        MaterialDynamicColors::tertiary_container().get_argb(self)
+    }
+
+    /// The `on_tertiary_container` role of this scheme as an ARGB integer.
+    pub fn on_tertiary_container(&self) -> u32 {
+        MaterialDynamicColors::on_tertiary_container().get_argb(self)
+    }
+
+    /// The `error` role of this scheme as an ARGB integer.
+    pub fn error(&self) -> u32 {
+        MaterialDynamicColors::error().get_argb(self)
+    }
+
+    /// The `on_error` role of this scheme as an ARGB integer.
+    pub fn on_error(&self) -> u32 {
+        MaterialDynamicColors::on_error().get_argb(self)
+    }
+
+    /// The `error_container` role of this scheme as an ARGB integer.
+    pub fn error_container(&self) -> u32 {
+        MaterialDynamicColors::error_container().get_argb(self)
+    }
+
+    /// The `on_error_container` role of this scheme as an ARGB integer.
+    pub fn on_error_container(&self) -> u32 {
+        MaterialDynamicColors::on_error_container().get_argb(self)
+    }
+
+    /// The `primary_fixed` role of this scheme as an ARGB integer.
+    pub fn primary_fixed(&self) -> u32 {
+        MaterialDynamicColors::primary_fixed().get_argb(self)
+    }
+
+    /// The `primary_fixed_dim` role of this scheme as an ARGB integer.
+    pub fn primary_fixed_dim(&self) -> u32 {
+        MaterialDynamicColors::primary_fixed_dim().get_argb(self)
+    }
+
+    /// The `on_primary_fixed` role of this scheme as an ARGB integer.
+    pub fn on_primary_fixed(&self) -> u32 {
+        MaterialDynamicColors::on_primary_fixed().get_argb(self)
+    }
+
+    /// The `on_primary_fixed_variant` role of this scheme as an ARGB integer.
+    pub fn on_primary_fixed_variant(&self) -> u32 {
+        MaterialDynamicColors::on_primary_fixed_variant().get_argb(self)
+    }
+
+    /// The `secondary_fixed` role of this scheme as an ARGB integer.
+    pub fn secondary_fixed(&self) -> u32 {
+        MaterialDynamicColors::secondary_fixed().get_argb(self)
+    }
+
+    /// The `secondary_fixed_dim` role of this scheme as an ARGB integer.
+    pub fn secondary_fixed_dim(&self) -> u32 {
+        MaterialDynamicColors::secondary_fixed_dim().get_argb(self)
+    }
+
+    /// The `on_secondary_fixed` role of this scheme as an ARGB integer.
+    pub fn on_secondary_fixed(&self) -> u32 {
+        MaterialDynamicColors::on_secondary_fixed().get_argb(self)
+    }
+
+    /// The `on_secondary_fixed_variant` role of this scheme as an ARGB integer.
+    pub fn on_secondary_fixed_variant(&self) -> u32 {
+        MaterialDynamicColors::on_secondary_fixed_variant().get_argb(self)
+    }
+
+    /// The `tertiary_fixed` role of this scheme as an ARGB integer.
+    pub fn tertiary_fixed(&self) -> u32 {
+        MaterialDynamicColors::tertiary_fixed().get_argb(self)
+    }
+
+    /// The `tertiary_fixed_dim` role of this scheme as an ARGB integer.
+    pub fn tertiary_fixed_dim(&self) -> u32 {
+        MaterialDynamicColors::tertiary_fixed_dim().get_argb(self)
+    }
+
+    /// The `on_tertiary_fixed` role of this scheme as an ARGB integer.
+    pub fn on_tertiary_fixed(&self) -> u32 {
+        MaterialDynamicColors::on_tertiary_fixed().get_argb(self)
+    }
+
+    /// The `on_tertiary_fixed_variant` role of this scheme as an ARGB integer.
+    pub fn on_tertiary_fixed_variant(&self) -> u32 {
+        MaterialDynamicColors::on_tertiary_fixed_variant().get_argb(self)
+    }
+}
@@ -1,3 +1,7 @@
+mod dynamic_scheme;
 mod scheme;
+mod variant;
 
+pub use dynamic_scheme::*;
 pub use scheme::*;
+pub use variant::*;
@@ -1,5 +1,17 @@
-use crate::palettes::CorePalette;
+#[cfg(feature = "serde")]
+use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use crate::utils::string_utils::hex_from_argb;
+use crate::{
+    dislike::DislikeAnalyzer,
+    palettes::{CorePalette, TonalPalette},
+    utils::color_utils::Argb,
+};
+
+use super::dynamic_scheme::DynamicScheme;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Scheme {
     pub primary: u32,
     pub on_primary: u32,
@@ -34,6 +46,29 @@ pub struct Scheme {
     pub inverse_primary: u32,
     pub scrim: u32,
     pub shadow: u32,
+    pub surface_tint: u32,
+    pub surface_variant: u32,
+    pub primary_fixed: u32,
+    pub primary_fixed_dim: u32,
+    pub on_primary_fixed: u32,
+    pub on_primary_fixed_variant: u32,
+    pub secondary_fixed: u32,
+    pub secondary_fixed_dim: u32,
+    pub on_secondary_fixed: u32,
+    pub on_secondary_fixed_variant: u32,
+    pub tertiary_fixed: u32,
+    pub tertiary_fixed_dim: u32,
+    pub on_tertiary_fixed: u32,
+    pub on_tertiary_fixed_variant: u32,
+}
+
+/// Moves a tone of `palette` off a disliked color, away from `on_tone` so the
+/// fixed on-color keeps at least its contrast. See `DislikeAnalyzer`.
+fn liked_tone(palette: &TonalPalette, tone: u32, on_tone: u32) -> u32 {
+    let (tone, on_tone) = (tone as f64, on_tone as f64);
+    DislikeAnalyzer::nearest_liked_tone(palette, tone, on_tone > tone, |candidate| {
+        (candidate - on_tone).abs() >= (tone - on_tone).abs()
+    }) as u32
 }
 
 impl Scheme {
@@ -42,15 +77,15 @@ impl Scheme {
         Scheme {
             primary: core.a1.tone(40),
             on_primary: core.a1.tone(100),
-            primary_container: core.a1.tone(90),
+            primary_container: core.a1.tone(liked_tone(&core.a1, 90, 10)),
             on_primary_container: core.a1.tone(10),
             secondary: core.a2.tone(40),
             on_secondary: core.a2.tone(100),
-            secondary_container: core.a2.tone(90),
+            secondary_container: core.a2.tone(liked_tone(&core.a2, 90, 10)),
             on_secondary_container: core.a2.tone(10),
-            tertiary: core.a3.tone(40),
+            tertiary: core.a3.tone(liked_tone(&core.a3, 40, 100)),
             on_tertiary: core.a3.tone(100),
-            tertiary_container: core.a3.tone(90),
+            tertiary_container: core.a3.tone(liked_tone(&core.a3, 90, 10)),
             on_tertiary_container: core.a3.tone(10),
             error: core.error.tone(40),
             on_error: core.error.tone(100),
@@ -73,6 +108,20 @@ impl Scheme {
             inverse_surface: core.n1.tone(20),
             inverse_on_surface: core.n1.tone(95),
             inverse_primary: core.a1.tone(80),
+            surface_tint: core.a1.tone(40),
+            surface_variant: core.n2.tone(90),
+            primary_fixed: core.a1.tone(90),
+            primary_fixed_dim: core.a1.tone(80),
+            on_primary_fixed: core.a1.tone(10),
+            on_primary_fixed_variant: core.a1.tone(30),
+            secondary_fixed: core.a2.tone(90),
+            secondary_fixed_dim: core.a2.tone(80),
+            on_secondary_fixed: core.a2.tone(10),
+            on_secondary_fixed_variant: core.a2.tone(30),
+            tertiary_fixed: core.a3.tone(90),
+            tertiary_fixed_dim: core.a3.tone(80),
+            on_tertiary_fixed: core.a3.tone(10),
+            on_tertiary_fixed_variant: core.a3.tone(30),
         }
     }
 
@@ -81,15 +130,15 @@ impl Scheme {
         Scheme {
             primary: core.a1.tone(80),
             on_primary: core.a1.tone(20),
-            primary_container: core.a1.tone(30),
+            primary_container: core.a1.tone(liked_tone(&core.a1, 30, 90)),
             on_primary_container: core.a1.tone(90),
             secondary: core.a2.tone(80),
             on_secondary: core.a2.tone(20),
-            secondary_container: core.a2.tone(30),
+            secondary_container: core.a2.tone(liked_tone(&core.a2, 30, 90)),
             on_secondary_container: core.a2.tone(90),
-            tertiary: core.a3.tone(80),
+            tertiary: core.a3.tone(liked_tone(&core.a3, 80, 20)),
             on_tertiary: core.a3.tone(20),
-            tertiary_container: core.a3.tone(30),
+            tertiary_container: core.a3.tone(liked_tone(&core.a3, 30, 90)),
             on_tertiary_container: core.a3.tone(90),
             error: core.error.tone(80),
             on_error: core.error.tone(20),
@@ -112,9 +161,58 @@ impl Scheme {
             inverse_surface: core.n1.tone(90),
             inverse_on_surface: core.n1.tone(20),
             inverse_primary: core.a1.tone(40),
+            surface_tint: core.a1.tone(80),
+            surface_variant: core.n2.tone(30),
+            primary_fixed: core.a1.tone(90),
+            primary_fixed_dim: core.a1.tone(80),
+            on_primary_fixed: core.a1.tone(10),
+            on_primary_fixed_variant: core.a1.tone(30),
+            secondary_fixed: core.a2.tone(90),
+            secondary_fixed_dim: core.a2.tone(80),
+            on_secondary_fixed: core.a2.tone(10),
+            on_secondary_fixed_variant: core.a2.tone(30),
+            tertiary_fixed: core.a3.tone(90),
+            tertiary_fixed_dim: core.a3.tone(80),
+            on_tertiary_fixed: core.a3.tone(10),
+            on_tertiary_fixed_variant: core.a3.tone(30),
         }
     }
 
+    /// Light scheme from core palette, adjusted for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `core` - The palettes to build the scheme from.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    pub fn light_from_core_palette_with_contrast(
+        core: &CorePalette,
+        contrast_level: f64,
+    ) -> Scheme {
+        Scheme::from_dynamic_scheme(&DynamicScheme::from_core_palette(
+            core,
+            false,
+            contrast_level,
+        ))
+    }
+
+    /// Dark scheme from core palette, adjusted for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `core` - The palettes to build the scheme from.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    pub fn dark_from_core_palette_with_contrast(core: &CorePalette, contrast_level: f64) -> Scheme {
+        Scheme::from_dynamic_scheme(&DynamicScheme::from_core_palette(
+            core,
+            true,
+            contrast_level,
+        ))
+    }
+
     /// Returns a light Material color scheme based on the color's hue.
     ///
     /// # Arguments
@@ -125,7 +223,20 @@ impl Scheme {
     ///
     /// A light Material color scheme.
     pub fn light(argb: u32) -> Scheme {
-        Scheme::light_from_core_palette(&CorePalette::of(argb))
+        Scheme::light_from_argb(Argb(argb))
+    }
+
+    /// Returns a light Material color scheme based on the color's hue.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    ///
+    /// # Returns
+    ///
+    /// A light Material color scheme.
+    pub fn light_from_argb(argb: Argb) -> Scheme {
+        Scheme::light_from_core_palette(&CorePalette::from_argb(argb))
     }
 
     /// Returns a dark Material color scheme based on the color's hue.
@@ -138,7 +249,88 @@ impl Scheme {
     ///
     /// A dark Material color scheme.
     pub fn dark(argb: u32) -> Scheme {
-        Scheme::dark_from_core_palette(&CorePalette::of(argb))
+        Scheme::dark_from_argb(Argb(argb))
+    }
+
+    /// Returns a dark Material color scheme based on the color's hue.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    ///
+    /// # Returns
+    ///
+    /// A dark Material color scheme.
+    pub fn dark_from_argb(argb: Argb) -> Scheme {
+        Scheme::dark_from_core_palette(&CorePalette::from_argb(argb))
+    }
+
+    /// Returns a light Material color scheme based on the color's hue, adjusted
+    /// for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - ARGB representation of a color.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    ///
+    /// # Returns
+    ///
+    /// A light Material color scheme.
+    pub fn light_with_contrast(argb: u32, contrast_level: f64) -> Scheme {
+        Scheme::light_from_argb_with_contrast(Argb(argb), contrast_level)
+    }
+
+    /// Returns a light Material color scheme based on the color's hue, adjusted
+    /// for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    ///
+    /// # Returns
+    ///
+    /// A light Material color scheme.
+    pub fn light_from_argb_with_contrast(argb: Argb, contrast_level: f64) -> Scheme {
+        Scheme::light_from_core_palette_with_contrast(&CorePalette::from_argb(argb), contrast_level)
+    }
+
+    /// Returns a dark Material color scheme based on the color's hue, adjusted
+    /// for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - ARGB representation of a color.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    ///
+    /// # Returns
+    ///
+    /// A dark Material color scheme.
+    pub fn dark_with_contrast(argb: u32, contrast_level: f64) -> Scheme {
+        Scheme::dark_from_argb_with_contrast(Argb(argb), contrast_level)
+    }
+
+    /// Returns a dark Material color scheme based on the color's hue, adjusted
+    /// for a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    /// * `contrast_level` - Value from -1 to 1. -1 represents minimum contrast,
+    ///   0 represents standard (i.e. the design as spec'd), and 1 represents
+    ///   maximum contrast.
+    ///
+    /// # Returns
+    ///
+    /// A dark Material color scheme.
+    pub fn dark_from_argb_with_contrast(argb: Argb, contrast_level: f64) -> Scheme {
+        Scheme::dark_from_core_palette_with_contrast(&CorePalette::from_argb(argb), contrast_level)
     }
 
     /// Returns a light Material content color scheme based on the color's hue.
@@ -151,7 +343,20 @@ impl Scheme {
     ///
     /// A light Material content color scheme.
     pub fn light_content(argb: u32) -> Scheme {
-        Scheme::light_from_core_palette(&CorePalette::content_of(argb))
+        Scheme::light_content_from_argb(Argb(argb))
+    }
+
+    /// Returns a light Material content color scheme based on the color's hue.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    ///
+    /// # Returns
+    ///
+    /// A light Material content color scheme.
+    pub fn light_content_from_argb(argb: Argb) -> Scheme {
+        Scheme::light_from_core_palette(&CorePalette::content_from_argb(argb))
     }
 
     /// Returns a dark Material content color scheme based on the color's hue.
@@ -164,6 +369,282 @@ impl Scheme {
     ///
     /// A dark Material content color scheme.
     pub fn dark_content(argb: u32) -> Scheme {
-        Scheme::dark_from_core_palette(&CorePalette::content_of(argb))
+        Scheme::dark_content_from_argb(Argb(argb))
+    }
+
+    /// Returns a dark Material content color scheme based on the color's hue.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb` - The color.
+    ///
+    /// # Returns
+    ///
+    /// A dark Material content color scheme.
+    pub fn dark_content_from_argb(argb: Argb) -> Scheme {
+        Scheme::dark_from_core_palette(&CorePalette::content_from_argb(argb))
+    }
+}
+
+/// Implements the functions that list the roles of a `Scheme`, look them up by
+/// name and resolve them from a `DynamicScheme`.
+macro_rules! scheme_roles {
+    ($($role:ident),* $(,)?) => {
+        impl Scheme {
+            /// The name of every role, in the order they are declared.
+            pub const ROLE_NAMES: &'static [&'static str] = &[$(stringify!($role)),*];
+
+            /// Returns the name and color of every role, in the order they are
+            /// declared.
+            pub fn roles(&self) -> Vec<(&'static str, u32)> {
+                vec![$((stringify!($role), self.$role)),*]
+            }
+
+            /// Creates a scheme from the roles of a `DynamicScheme`, with each
+            /// role's tone resolved for the scheme's contrast level.
+            ///
+            /// # Arguments
+            ///
+            /// * `scheme` - The dynamic scheme to resolve.
+            ///
+            /// # Returns
+            ///
+            /// A Material color scheme.
+            pub fn from_dynamic_scheme(scheme: &DynamicScheme) -> Scheme {
+                Scheme {
+                    $($role: scheme.$role(),)*
+                }
+            }
+
+            /// Creates a scheme from the color of every role, looked up by name.
+            ///
+            /// # Arguments
+            ///
+            /// * `color` - Returns the color of the role with the given name,
+            ///   ex. "on_primary", or `None` when the role has no color.
+            ///
+            /// # Returns
+            ///
+            /// The scheme, or the name of the first role without a color.
+            pub fn try_from_roles(
+                mut color: impl FnMut(&'static str) -> Option<u32>,
+            ) -> Result<Scheme, &'static str> {
+                Ok(Scheme {
+                    $($role: color(stringify!($role)).ok_or(stringify!($role))?,)*
+                })
+            }
+        }
+    };
+}
+
+scheme_roles!(
+    primary,
+    on_primary,
+    primary_container,
+    on_primary_container,
+    secondary,
+    on_secondary,
+    secondary_container,
+    on_secondary_container,
+    tertiary,
+    on_tertiary,
+    tertiary_container,
+    on_tertiary_container,
+    error,
+    on_error,
+    error_container,
+    on_error_container,
+    surface_dim,
+    surface,
+    surface_bright,
+    surface_container_lowest,
+    surface_container_low,
+    surface_container,
+    surface_container_high,
+    surface_container_highest,
+    on_surface,
+    on_surface_variant,
+    outline,
+    outline_variant,
+    inverse_surface,
+    inverse_on_surface,
+    inverse_primary,
+    scrim,
+    shadow,
+    surface_tint,
+    surface_variant,
+    primary_fixed,
+    primary_fixed_dim,
+    on_primary_fixed,
+    on_primary_fixed_variant,
+    secondary_fixed,
+    secondary_fixed_dim,
+    on_secondary_fixed,
+    on_secondary_fixed_variant,
+    tertiary_fixed,
+    tertiary_fixed_dim,
+    on_tertiary_fixed,
+    on_tertiary_fixed_variant,
+);
+
+/// Serializes a scheme as a map from the name of each role to its color as an
+/// #rrggbb string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Scheme {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.roles()
+                .into_iter()
+                .map(|(role, argb)| (role, hex_from_argb(argb))),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Scheme {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut colors: HashMap<String, Argb> = serde::Deserialize::deserialize(deserializer)?;
+        Scheme::try_from_roles(|role| colors.remove(role).map(u32::from))
+            .map_err(D::Error::missing_field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        contrast::ratio_of_argb,
+        dynamiccolor::{DynamicColor, MaterialDynamicColors},
+        hct::Hct,
+    };
+
+    #[test]
+    fn contrast_schemes_meet_contrast_curve() {
+        const FOREGROUNDS: [fn() -> DynamicColor; 22] = [
+            MaterialDynamicColors::on_surface,
+            MaterialDynamicColors::on_surface_variant,
+            MaterialDynamicColors::inverse_on_surface,
+            MaterialDynamicColors::outline,
+            MaterialDynamicColors::outline_variant,
+            MaterialDynamicColors::primary,
+            MaterialDynamicColors::on_primary,
+            MaterialDynamicColors::primary_container,
+            MaterialDynamicColors::on_primary_container,
+            MaterialDynamicColors::inverse_primary,
+            MaterialDynamicColors::secondary,
+            MaterialDynamicColors::on_secondary,
+            MaterialDynamicColors::secondary_container,
+            MaterialDynamicColors::on_secondary_container,
+            MaterialDynamicColors::tertiary,
+            MaterialDynamicColors::on_tertiary,
+            MaterialDynamicColors::tertiary_container,
+            MaterialDynamicColors::on_tertiary_container,
+            MaterialDynamicColors::error,
+            MaterialDynamicColors::on_error,
+            MaterialDynamicColors::error_container,
+            MaterialDynamicColors::on_error_container,
+        ];
+        let mut failures = vec![];
+        for seed in [0xff4285f4, 0xffff0000, 0xffffff00, 0xff00ff00, 0xff808080] {
+            let core = CorePalette::of(seed);
+            for contrast_level in [-1.0, 0.0, 0.5, 1.0] {
+                for is_dark in [false, true] {
+                    let schemes = if is_dark {
+                        [
+                            Scheme::dark_with_contrast(seed, contrast_level),
+                            Scheme::dark_from_core_palette_with_contrast(&core, contrast_level),
+                        ]
+                    } else {
+                        [
+                            Scheme::light_with_contrast(seed, contrast_level),
+                            Scheme::light_from_core_palette_with_contrast(&core, contrast_level),
+                        ]
+                    };
+                    // Only used to name the background of each foreground.
+                    let dynamic_scheme =
+                        DynamicScheme::from_core_palette(&core, is_dark, contrast_level);
+                    for scheme in schemes {
+                        let roles: HashMap<_, _> = scheme.roles().into_iter().collect();
+                        for foreground in FOREGROUNDS.map(|color| color()) {
+                            let target = foreground.contrast_curve.unwrap().get(contrast_level);
+                            let argb = roles[foreground.name];
+                            let backgrounds = [foreground.background, foreground.second_background];
+                            for background in backgrounds.into_iter().flatten() {
+                                let name = background(&dynamic_scheme).name;
+                                let bg_argb = roles[name];
+                                let ratio = ratio_of_argb(argb, bg_argb);
+                                let best = ratio_of_argb(0xff000000, bg_argb)
+                                    .max(ratio_of_argb(0xffffffff, bg_argb));
+                                if ratio < target.min(best) {
+                                    failures.push(format!(
+                                        "{:08x} contrast {} dark {}: {} on {} is {:.2}, wants {:.2}",
+                                        seed, contrast_level, is_dark,
+                                        foreground.name, name, ratio, target
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn core_palette_schemes_avoid_disliked_colors() {
+        // Dark yellow-greens, which `DislikeAnalyzer` dislikes, plus yellow.
+        for seed in [0xff6d5e00, 0xff554500, 0xff8a8647, 0xffffff00] {
+            let core = CorePalette::of(seed);
+            for scheme in [
+                Scheme::light_from_core_palette(&core),
+                Scheme::dark_from_core_palette(&core),
+            ] {
+                for (role, argb) in [
+                    ("primary_container", scheme.primary_container),
+                    ("secondary_container", scheme.secondary_container),
+                    ("tertiary", scheme.tertiary),
+                    ("tertiary_container", scheme.tertiary_container),
+                ] {
+                    let hct = Hct::from_int(argb);
+                    assert!(
+                        !DislikeAnalyzer::is_disliked(&hct),
+                        "{seed:08x} {role} {argb:08x}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_round_trips_hex_map() {
+        let scheme = Scheme::light(0xff4285f4);
+        let json = serde_json::to_value(scheme).unwrap();
+        let map = json.as_object().unwrap();
+        assert_eq!(map.len(), Scheme::ROLE_NAMES.len());
+        assert_eq!(map["primary"], hex_from_argb(scheme.primary));
+        assert_eq!(serde_json::from_value::<Scheme>(json).unwrap(), scheme);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_rejects_missing_role() {
+        let mut json = serde_json::to_value(Scheme::dark(0xff4285f4)).unwrap();
+        json.as_object_mut().unwrap().remove("on_surface");
+        let error = serde_json::from_value::<Scheme>(json).unwrap_err();
+        assert_eq!(error.to_string(), "missing field `on_surface`");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_rejects_invalid_color() {
+        let mut json = serde_json::to_value(Scheme::dark(0xff4285f4)).unwrap();
+        json["primary"] = "#12345".into();
+        assert!(serde_json::from_value::<Scheme>(json).is_err());
     }
 }
@@ -0,0 +1,49 @@
+/// Set of themes supported by Dynamic Color. Pass one to `DynamicScheme::new`
+/// to create the palettes corresponding to the theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// A monochrome theme, colors are purely black / white / gray.
+    Monochrome,
+
+    /// A theme that's slightly more chromatic than monochrome, which is purely
+    /// black / white / gray.
+    Neutral,
+
+    /// A calm theme, sedated colors that aren't particularly chromatic.
+    TonalSpot,
+
+    /// A loud theme, colorfulness is maximum for Primary palette, increased for
+    /// others.
+    Vibrant,
+
+    /// A playful theme - the source color's hue does not appear in the theme.
+    Expressive,
+
+    /// A scheme that places the source color in the primary container.
+    ///
+    /// Primary Container is the source color, adjusted for color relativity.
+    /// It maintains constant appearance in light mode and dark mode. This adds
+    /// ~5 tone in light mode, and subtracts ~5 tone in dark mode.
+    ///
+    /// Tertiary Container is the complement to the source color, using
+    /// `TemperatureCache`. It also maintains constant appearance.
+    Fidelity,
+
+    /// A scheme that places the source color in the primary container.
+    ///
+    /// Primary Container is the source color, adjusted for color relativity.
+    /// It maintains constant appearance in light mode and dark mode. This adds
+    /// ~5 tone in light mode, and subtracts ~5 tone in dark mode.
+    ///
+    /// Tertiary Container is an analogous color, specifically, the analog of a
+    /// color wheel divided into 6, and the precise analog is the one found by
+    /// increasing hue. This is a scientifically grounded equivalent to rotating
+    /// hue clockwise by 60 degrees. It also maintains constant appearance.
+    Content,
+
+    /// A playful theme - the source color's hue does not appear in the theme.
+    Rainbow,
+
+    /// A playful theme - the source color's hue does not appear in the theme.
+    FruitSalad,
+}
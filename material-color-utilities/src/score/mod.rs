@@ -0,0 +1,3 @@
+mod score;
+
+pub use score::*;
@@ -0,0 +1,336 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    hct::Hct,
+    utils::math_utils::{difference_degrees, sanitize_degrees_int},
+};
+
+const TARGET_CHROMA: f64 = 48.0; // A1 Chroma
+const WEIGHT_PROPORTION: f64 = 0.7;
+const WEIGHT_CHROMA_ABOVE: f64 = 0.3;
+const WEIGHT_CHROMA_BELOW: f64 = 0.1;
+const CUTOFF_CHROMA: f64 = 5.0;
+const CUTOFF_EXCITED_PROPORTION: f64 = 0.01;
+
+/// Options used when ranking colors with `Score::score_with_options`
+pub struct ScoreOptions {
+    /// Max count of colors to be returned in the list.
+    pub desired: usize,
+
+    /// Color to be returned if no other options are available.
+    pub fallback_color_argb: u32,
+
+    /// Whether to filter out undesireable combinations.
+    pub filter: bool,
+}
+
+impl Default for ScoreOptions {
+    fn default() -> Self {
+        ScoreOptions {
+            desired: 4,
+            // Google Blue.
+            fallback_color_argb: 0xff4285f4,
+            filter: true,
+        }
+    }
+}
+
+struct ScoredHct {
+    hct: Hct,
+    score: f64,
+}
+
+/// Given a large set of colors, remove colors that are unsuitable for a UI
+/// theme, and rank the rest based on suitability.
+///
+/// Enables use of a high cluster count for image quantization, thus ensuring
+/// colors aren't muddied, while curating the high cluster count to a much
+/// smaller number of appropriate choices.
+pub struct Score;
+
+impl Score {
+    /// Given a map with keys of colors and values of how often the color
+    /// appears, rank the colors based on suitability for being used for a UI
+    /// theme, using the default `ScoreOptions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors_to_population` - map with keys of colors and values of how
+    ///   often the color appears, usually from a source image.
+    ///
+    /// # Returns
+    ///
+    /// Colors sorted by suitability for a UI theme. The most suitable color is
+    /// the first item, the least suitable is the last. There will always be at
+    /// least one color returned. If all the input colors were not suitable for
+    /// a theme, a default fallback color will be provided, Google Blue.
+    pub fn score(colors_to_population: &HashMap<u32, u32>) -> Vec<u32> {
+        Score::score_with_options(colors_to_population, &ScoreOptions::default())
+    }
+
+    /// Given a map with keys of colors and values of how often the color
+    /// appears, rank the colors based on suitability for being used for a UI
+    /// theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors_to_population` - map with keys of colors and values of how
+    ///   often the color appears, usually from a source image.
+    /// * `options` - the desired count, fallback color and filtering to use.
+    ///
+    /// # Returns
+    ///
+    /// Colors sorted by suitability for a UI theme. The most suitable color is
+    /// the first item, the least suitable is the last. There will always be at
+    /// least one color returned. If all the input colors were not suitable for
+    /// a theme, `options.fallback_color_argb` will be provided.
+    pub fn score_with_options(
+        colors_to_population: &HashMap<u32, u32>,
+        options: &ScoreOptions,
+    ) -> Vec<u32> {
+        // Get the HCT color for each Argb value, while finding the per hue count and
+        // total count.
+        let mut colors_hct = Vec::with_capacity(colors_to_population.len());
+        let mut hue_population = [0_u64; 360];
+        let mut population_sum = 0.0;
+        for (&argb, &population) in colors_to_population {
+            let hct = Hct::from_int(argb);
+            let hue = hct.hue().floor() as usize;
+            hue_population[hue % 360] += population as u64;
+            population_sum += population as f64;
+            colors_hct.push(hct);
+        }
+
+        // Hues with more usage in neighboring 30 degree slice get a larger number.
+        let mut hue_excited_proportions = [0.0; 360];
+        for (hue, &population) in hue_population.iter().enumerate() {
+            let proportion = population as f64 / population_sum;
+            for i in (hue as i32 - 14)..(hue as i32 + 16) {
+                let neighbor_hue = sanitize_degrees_int(i) as usize;
+                hue_excited_proportions[neighbor_hue] += proportion;
+            }
+        }
+
+        // Scores each HCT color based on usage and chroma, while optionally
+        // filtering out values that do not have enough chroma or usage.
+        let mut scored_hcts = Vec::new();
+        for hct in colors_hct {
+            let hue = sanitize_degrees_int(hct.hue().round() as i32) as usize;
+            let proportion = hue_excited_proportions[hue];
+            if options.filter
+                && (hct.chroma() < CUTOFF_CHROMA || proportion <= CUTOFF_EXCITED_PROPORTION)
+            {
+                continue;
+            }
+
+            let proportion_score = proportion * 100.0 * WEIGHT_PROPORTION;
+            let chroma_weight = if hct.chroma() < TARGET_CHROMA {
+                WEIGHT_CHROMA_BELOW
+            } else {
+                WEIGHT_CHROMA_ABOVE
+            };
+            let chroma_score = (hct.chroma() - TARGET_CHROMA) * chroma_weight;
+            let score = proportion_score + chroma_score;
+            scored_hcts.push(ScoredHct { hct, score });
+        }
+
+        // Sorted so that colors with higher scores come first. Ties are broken by
+        // the color itself, so the result does not depend on map iteration order.
+        scored_hcts.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.hct.to_int().cmp(&b.hct.to_int()))
+        });
+
+        // Iterates through potential hue differences in degrees in order to select
+        // the colors with the largest distribution of hues possible. Starting at
+        // 90 degrees(maximum difference for 4 colors) then decreasing down to a
+        // 15 degree minimum.
+        let mut chosen_colors: Vec<&Hct> = Vec::new();
+        for difference in (15..=90).rev() {
+            chosen_colors.clear();
+            for entry in &scored_hcts {
+                let has_duplicate_hue = chosen_colors.iter().any(|chosen_hct| {
+                    difference_degrees(entry.hct.hue(), chosen_hct.hue()) < difference as f64
+                });
+                if !has_duplicate_hue {
+                    chosen_colors.push(&entry.hct);
+                }
+                if chosen_colors.len() >= options.desired {
+                    break;
+                }
+            }
+            if chosen_colors.len() >= options.desired {
+                break;
+            }
+        }
+
+        if chosen_colors.is_empty() {
+            return vec![options.fallback_color_argb];
+        }
+
+        chosen_colors.iter().map(|hct| hct.to_int()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(
+        colors: &[(u32, u32)],
+        desired: usize,
+        fallback_color_argb: u32,
+        filter: bool,
+    ) -> Vec<u32> {
+        Score::score_with_options(
+            &HashMap::from_iter(colors.iter().copied()),
+            &ScoreOptions {
+                desired,
+                fallback_color_argb,
+                filter,
+            },
+        )
+    }
+
+    #[test]
+    fn prioritizes_chroma() {
+        let colors = HashMap::from([(0xff000000, 1), (0xffffffff, 1), (0xff0000ff, 1)]);
+        assert_eq!(Score::score(&colors), vec![0xff0000ff]);
+    }
+
+    #[test]
+    fn prioritizes_chroma_when_proportions_equal() {
+        let colors = HashMap::from([(0xffff0000, 1), (0xff00ff00, 1), (0xff0000ff, 1)]);
+        assert_eq!(
+            Score::score(&colors),
+            vec![0xffff0000, 0xff00ff00, 0xff0000ff]
+        );
+    }
+
+    #[test]
+    fn generates_google_blue_when_no_colors_available() {
+        let colors = HashMap::from([(0xff000000, 1)]);
+        assert_eq!(Score::score(&colors), vec![0xff4285f4]);
+    }
+
+    #[test]
+    fn dedupes_nearby_hues() {
+        let colors = HashMap::from([(0xff008772, 1), (0xff318477, 1)]);
+        assert_eq!(Score::score(&colors), vec![0xff008772]);
+    }
+
+    #[test]
+    fn maximizes_hue_distance() {
+        let colors = [(0xff008772, 1), (0xff008587, 1), (0xff007ebc, 1)];
+        assert_eq!(
+            score(&colors, 2, 0xff4285f4, true),
+            vec![0xff007ebc, 0xff008772]
+        );
+    }
+
+    /// Scenarios modeled on the reference implementation's tests.
+    #[test]
+    fn generated_scenarios() {
+        assert_eq!(
+            score(
+                &[(0xff7ea16d, 67), (0xffd8ccae, 67), (0xff835c0d, 49)],
+                3,
+                0xff8d3819,
+                false
+            ),
+            vec![0xff7ea16d, 0xffd8ccae, 0xff835c0d]
+        );
+        assert_eq!(
+            score(
+                &[
+                    (0xffd33881, 14),
+                    (0xff3205cc, 77),
+                    (0xff0b48cf, 36),
+                    (0xffa08f5d, 81)
+                ],
+                4,
+                0xff7d772b,
+                true
+            ),
+            vec![0xff3205cc, 0xffa08f5d, 0xffd33881]
+        );
+        assert_eq!(
+            score(
+                &[
+                    (0xffbe94a6, 23),
+                    (0xffc33fd7, 42),
+                    (0xff899f36, 90),
+                    (0xff94c574, 82)
+                ],
+                3,
+                0xffaa79a4,
+                true
+            ),
+            vec![0xff94c574, 0xffc33fd7, 0xffbe94a6]
+        );
+        // 0xffd06d5f and 0xff713090 are within 15 degrees of hue of higher
+        // scoring colors, so fewer colors than desired are returned.
+        assert_eq!(
+            score(
+                &[
+                    (0xffdf241c, 85),
+                    (0xff685859, 44),
+                    (0xffd06d5f, 34),
+                    (0xff561c54, 27),
+                    (0xff713090, 88)
+                ],
+                5,
+                0xff58c19c,
+                false
+            ),
+            vec![0xffdf241c, 0xff561c54]
+        );
+        assert_eq!(
+            score(
+                &[
+                    (0xffbe66f8, 41),
+                    (0xff4bbda9, 88),
+                    (0xff80f6f9, 44),
+                    (0xffab8017, 43),
+                    (0xffe89307, 65)
+                ],
+                3,
+                0xff916691,
+                false
+            ),
+            vec![0xffab8017, 0xff4bbda9, 0xffbe66f8]
+        );
+        assert_eq!(
+            score(
+                &[
+                    (0xff18ea8f, 93),
+                    (0xff327593, 18),
+                    (0xff066a18, 53),
+                    (0xfffa8a23, 74),
+                    (0xff04ca1f, 62)
+                ],
+                2,
+                0xff4c377a,
+                false
+            ),
+            vec![0xff18ea8f, 0xfffa8a23]
+        );
+        assert_eq!(
+            score(
+                &[
+                    (0xff2e05ed, 23),
+                    (0xff153e55, 90),
+                    (0xff9ab220, 23),
+                    (0xff153379, 66),
+                    (0xff68bcc3, 81)
+                ],
+                2,
+                0xfff588dc,
+                true
+            ),
+            vec![0xff2e05ed, 0xff9ab220]
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod temperature_cache;
+
+pub use temperature_cache::*;
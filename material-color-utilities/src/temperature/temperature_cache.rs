@@ -0,0 +1,403 @@
+use std::{cell::OnceCell, cmp::Ordering};
+
+use crate::{
+    hct::Hct,
+    utils::{
+        color_utils::lab_from_argb,
+        math_utils::{sanitize_degrees_double, sanitize_degrees_int},
+    },
+};
+
+/// Design utilities using color temperature theory.
+///
+/// Analogous colors, complementary color, and cache to efficiently, lazily,
+/// generate data for calculations when needed.
+pub struct TemperatureCache {
+    input: Hct,
+
+    /// Colors with the input's chroma and tone at every integer hue from 0 to
+    /// 360 inclusive, followed by the input color itself.
+    hcts: OnceCell<Vec<Hct>>,
+
+    /// Raw temperature of each color in `hcts`.
+    temps: OnceCell<Vec<f64>>,
+
+    /// Indexes into `hcts`, sorted from coldest to warmest.
+    by_temp: OnceCell<Vec<usize>>,
+
+    complement: OnceCell<u32>,
+    input_relative_temperature: OnceCell<f64>,
+}
+
+impl TemperatureCache {
+    /// Create a cache that allows calculation of ex. complementary and
+    /// analogous colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Color to find complement/analogous colors of. Any colors
+    ///   will have the same tone, and chroma as the input color, modulo any
+    ///   restrictions due to the other hues having lower limits on chroma.
+    pub fn new(input: &Hct) -> TemperatureCache {
+        TemperatureCache {
+            input: Hct::from_int(input.to_int()),
+            hcts: OnceCell::new(),
+            temps: OnceCell::new(),
+            by_temp: OnceCell::new(),
+            complement: OnceCell::new(),
+            input_relative_temperature: OnceCell::new(),
+        }
+    }
+
+    /// A color that complements the input color aesthetically.
+    ///
+    /// In art, this is usually described as being across the color wheel.
+    /// History of this shows intent as a color that is just as cool-warm as the
+    /// input color is warm-cool.
+    pub fn complement(&self) -> Hct {
+        let argb = *self
+            .complement
+            .get_or_init(|| self.hcts()[self.complement_index()].to_int());
+        Hct::from_int(argb)
+    }
+
+    fn complement_index(&self) -> usize {
+        let temps = self.temps();
+        let coldest = self.coldest_index();
+        let warmest = self.warmest_index();
+        let coldest_hue = self.hcts()[coldest].hue();
+        let coldest_temp = temps[coldest];
+        let warmest_hue = self.hcts()[warmest].hue();
+        let warmest_temp = temps[warmest];
+        let range = warmest_temp - coldest_temp;
+        let start_hue_is_coldest_to_warmest =
+            TemperatureCache::is_between(self.input.hue(), coldest_hue, warmest_hue);
+        let start_hue = if start_hue_is_coldest_to_warmest {
+            warmest_hue
+        } else {
+            coldest_hue
+        };
+        let end_hue = if start_hue_is_coldest_to_warmest {
+            coldest_hue
+        } else {
+            warmest_hue
+        };
+        let direction_of_rotation = 1.0;
+        let mut smallest_error = 1000.0;
+        let mut answer = self.input.hue().round() as usize;
+
+        let complement_relative_temp = 1.0 - self.input_relative_temperature();
+        // Find the color in the other section, closest to the inverse percentile
+        // of the input color. This is the complement.
+        for hue_addend in 0..=360 {
+            let hue =
+                sanitize_degrees_double(start_hue + direction_of_rotation * hue_addend as f64);
+            if !TemperatureCache::is_between(hue, start_hue, end_hue) {
+                continue;
+            }
+            let possible_answer = hue.round() as usize;
+            let relative_temp = (temps[possible_answer] - coldest_temp) / range;
+            let error = (complement_relative_temp - relative_temp).abs();
+            if error < smallest_error {
+                smallest_error = error;
+                answer = possible_answer;
+            }
+        }
+
+        answer
+    }
+
+    /// A set of colors with differing hues, equidistant in temperature.
+    ///
+    /// In art, this is usually described as a set of 5 colors on a color wheel
+    /// divided into 12 sections. This method allows provision of either of
+    /// those values.
+    ///
+    /// When `count` is 0, only the input color is returned. When `divisions`
+    /// is less than `count`, including 0, colors repeat.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of colors to return, includes the input color.
+    /// * `divisions` - The number of divisions on the color wheel.
+    pub fn analogous(&self, count: usize, divisions: usize) -> Vec<Hct> {
+        // The starting hue is the hue of the input color.
+        let start_hue = self.input.hue().round() as i32;
+        let start_index = sanitize_degrees_int(start_hue) as usize;
+        let mut last_temp = self.relative_temperature_at(start_index);
+
+        let mut all_colors = vec![start_index];
+
+        let mut absolute_total_temp_delta = 0.0;
+        for i in 0..360 {
+            let hue = sanitize_degrees_int(start_hue + i) as usize;
+            let temp = self.relative_temperature_at(hue);
+            let temp_delta = (temp - last_temp).abs();
+            last_temp = temp;
+            absolute_total_temp_delta += temp_delta;
+        }
+
+        let mut hue_addend = 1;
+        let temp_step = absolute_total_temp_delta / divisions as f64;
+        let mut total_temp_delta = 0.0;
+        last_temp = self.relative_temperature_at(start_index);
+        while all_colors.len() < divisions {
+            let hue = sanitize_degrees_int(start_hue + hue_addend) as usize;
+            let temp = self.relative_temperature_at(hue);
+            let temp_delta = (temp - last_temp).abs();
+            total_temp_delta += temp_delta;
+
+            let mut desired_total_temp_delta_for_index = all_colors.len() as f64 * temp_step;
+            let mut index_satisfied = total_temp_delta >= desired_total_temp_delta_for_index;
+            let mut index_addend = 1;
+            // Keep adding this hue to the answers until its temperature is
+            // insufficient. This ensures consistent behavior when there aren't
+            // `divisions` discrete steps between 0 and 360 in hue with
+            // `temp_step` delta in temperature between them.
+            //
+            // For example, white and black have no analogues: there are no other
+            // colors at T100/T0. Therefore, they should just be added to the
+            // array as answers.
+            while index_satisfied && all_colors.len() < divisions {
+                all_colors.push(hue);
+                desired_total_temp_delta_for_index =
+                    (all_colors.len() + index_addend) as f64 * temp_step;
+                index_satisfied = total_temp_delta >= desired_total_temp_delta_for_index;
+                index_addend += 1;
+            }
+            last_temp = temp;
+            hue_addend += 1;
+
+            if hue_addend > 360 {
+                while all_colors.len() < divisions {
+                    all_colors.push(hue);
+                }
+                break;
+            }
+        }
+
+        let mut answers = vec![self.hct_at(self.input_index())];
+
+        let ccw_count = (count.saturating_sub(1)) / 2;
+        for i in 1..(ccw_count + 1) {
+            let index = (all_colors.len() - i % all_colors.len()) % all_colors.len();
+            answers.insert(0, self.hct_at(all_colors[index]));
+        }
+
+        let cw_count = count.saturating_sub(ccw_count + 1);
+        for i in 1..(cw_count + 1) {
+            let index = i % all_colors.len();
+            answers.push(self.hct_at(all_colors[index]));
+        }
+
+        answers
+    }
+
+    /// 5 colors that pair well with the input color.
+    ///
+    /// The colors are equidistant in temperature and adjacent in hue.
+    pub fn analogous_colors(&self) -> Vec<Hct> {
+        self.analogous(5, 12)
+    }
+
+    /// Temperature relative to all colors with the same chroma and tone.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - HCT to find the relative temperature of.
+    ///
+    /// # Returns
+    ///
+    /// Value on a scale from 0 to 1.
+    pub fn relative_temperature(&self, target: &Hct) -> f64 {
+        self.relative_to_range(TemperatureCache::raw_temperature(target))
+    }
+
+    /// Relative temperature of the input color. See `relative_temperature`.
+    pub fn input_relative_temperature(&self) -> f64 {
+        *self
+            .input_relative_temperature
+            .get_or_init(|| self.relative_temperature_at(self.input_index()))
+    }
+
+    /// HCTs for all colors with the same chroma/tone as the input, plus the
+    /// input itself, sorted from coldest first to warmest last.
+    pub fn hcts_by_temp(&self) -> Vec<Hct> {
+        self.by_temp()
+            .iter()
+            .map(|&index| self.hct_at(index))
+            .collect()
+    }
+
+    /// Warmest color with same chroma and tone as input.
+    pub fn warmest(&self) -> Hct {
+        self.hct_at(self.warmest_index())
+    }
+
+    /// Coldest color with same chroma and tone as input.
+    pub fn coldest(&self) -> Hct {
+        self.hct_at(self.coldest_index())
+    }
+
+    fn relative_temperature_at(&self, index: usize) -> f64 {
+        self.relative_to_range(self.temps()[index])
+    }
+
+    /// Scales a raw temperature to the range between the coldest and warmest
+    /// colors, from 0 to 1.
+    fn relative_to_range(&self, temperature: f64) -> f64 {
+        let temps = self.temps();
+        let coldest_temp = temps[self.coldest_index()];
+        let range = temps[self.warmest_index()] - coldest_temp;
+        let difference_from_coldest = temperature - coldest_temp;
+        // Handle when there's no difference in temperature between warmest and
+        // coldest: for example, at T100, only one color is available, white.
+        if range == 0.0 {
+            return 0.5;
+        }
+        difference_from_coldest / range
+    }
+
+    /// Value representing cool-warm factor of a color. Values below 0 are
+    /// considered cool, above, warm.
+    ///
+    /// Color science has researched emotion and harmony, which art uses to
+    /// select colors. Warm-cool is the foundation of analogous and
+    /// complementary colors. See:
+    /// - Li-Chen Ou's Chapter 19 in Handbook of Color Psychology (2015).
+    /// - Josef Albers' Interaction of Color chapters 19 and 21.
+    ///
+    /// Implementation of Ou, Woodcock and Wright's algorithm, which uses
+    /// L*a*b* / LCH color space. Return value has these properties:
+    /// - Values below 0 are cool, above 0 are warm.
+    /// - Lower bound: -0.52 - (chroma ^ 1.07 / 20). L*a*b* chroma is
+    ///   infinite. Assuming max of 130 chroma, -9.66.
+    /// - Upper bound: -0.52 + (chroma ^ 1.07 / 20). L*a*b* chroma is
+    ///   infinite. Assuming max of 130 chroma, 8.61.
+    pub fn raw_temperature(color: &Hct) -> f64 {
+        let lab = lab_from_argb(color.to_int());
+        let hue = sanitize_degrees_double(lab[2].atan2(lab[1]).to_degrees());
+        let chroma = lab[1].hypot(lab[2]);
+        -0.5 + 0.02 * chroma.powf(1.07) * sanitize_degrees_double(hue - 50.0).to_radians().cos()
+    }
+
+    /// Determines if an angle is between two other angles, rotating clockwise.
+    pub fn is_between(angle: f64, a: f64, b: f64) -> bool {
+        if a < b {
+            return a <= angle && angle <= b;
+        }
+        a <= angle || angle <= b
+    }
+
+    fn hcts(&self) -> &[Hct] {
+        self.hcts.get_or_init(|| {
+            let mut hcts: Vec<Hct> = (0..=360)
+                .map(|hue| Hct::from_hct(hue as f64, self.input.chroma(), self.input.tone()))
+                .collect();
+            hcts.push(Hct::from_int(self.input.to_int()));
+            hcts
+        })
+    }
+
+    fn temps(&self) -> &[f64] {
+        self.temps.get_or_init(|| {
+            self.hcts()
+                .iter()
+                .map(TemperatureCache::raw_temperature)
+                .collect()
+        })
+    }
+
+    fn by_temp(&self) -> &[usize] {
+        self.by_temp.get_or_init(|| {
+            let temps = self.temps();
+            let mut by_temp: Vec<usize> = (0..temps.len()).collect();
+            by_temp.sort_by(|&a, &b| temps[a].partial_cmp(&temps[b]).unwrap_or(Ordering::Equal));
+            by_temp
+        })
+    }
+
+    fn input_index(&self) -> usize {
+        self.hcts().len() - 1
+    }
+
+    fn coldest_index(&self) -> usize {
+        self.by_temp()[0]
+    }
+
+    fn warmest_index(&self) -> usize {
+        let by_temp = self.by_temp();
+        by_temp[by_temp.len() - 1]
+    }
+
+    fn hct_at(&self, index: usize) -> Hct {
+        Hct::from_int(self.hcts()[index].to_int())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analogous(argb: u32, count: usize, divisions: usize) -> Vec<u32> {
+        TemperatureCache::new(&Hct::from_int(argb))
+            .analogous(count, divisions)
+            .iter()
+            .map(Hct::to_int)
+            .collect()
+    }
+
+    #[test]
+    fn raw_temperature() {
+        let raw = |argb| TemperatureCache::raw_temperature(&Hct::from_int(argb));
+        assert!((raw(0xff0000ff) - -1.393).abs() < 0.001);
+        assert!((raw(0xffff0000) - 2.351).abs() < 0.001);
+        assert!((raw(0xff00ff00) - -0.267).abs() < 0.001);
+        assert!((raw(0xffffffff) - -0.5).abs() < 0.001);
+        assert!((raw(0xff000000) - -0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn complement() {
+        let complement = |argb| {
+            TemperatureCache::new(&Hct::from_int(argb))
+                .complement()
+                .to_int()
+        };
+        assert_eq!(complement(0xff0000ff), 0xff9d0002);
+        assert_eq!(complement(0xffff0000), 0xff007bfc);
+        assert_eq!(complement(0xff00ff00), 0xffffd2c9);
+        assert_eq!(complement(0xffffffff), 0xffffffff);
+        assert_eq!(complement(0xff000000), 0xff000000);
+    }
+
+    #[test]
+    fn analogous_colors() {
+        assert_eq!(
+            analogous(0xff0000ff, 5, 12),
+            vec![0xff00590c, 0xff00564e, 0xff0000ff, 0xff6700cc, 0xff81009f]
+        );
+        assert_eq!(
+            analogous(0xffff0000, 5, 12),
+            vec![0xfff60082, 0xfffc004c, 0xffff0000, 0xffd95500, 0xffaf7200]
+        );
+        assert_eq!(
+            analogous(0xff00ff00, 5, 12),
+            vec![0xffcee900, 0xff92f500, 0xff00ff00, 0xff00fd6f, 0xff00fab3]
+        );
+        assert_eq!(analogous(0xff000000, 5, 12), vec![0xff000000; 5]);
+        assert_eq!(analogous(0xffffffff, 5, 12), vec![0xffffffff; 5]);
+    }
+
+    #[test]
+    fn analogous_with_no_colors_or_divisions() {
+        assert_eq!(analogous(0xff0000ff, 0, 12), vec![0xff0000ff]);
+        assert_eq!(analogous(0xff0000ff, 1, 12), vec![0xff0000ff]);
+
+        // Every division repeats the color at the input's hue.
+        let colors = analogous(0xff0000ff, 3, 0);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[1], 0xff0000ff);
+        assert_eq!(colors[0], colors[2]);
+    }
+}
@@ -204,6 +204,7 @@ pub fn white_point_d65() -> [f64; 3] {
 }
 
 /// RGBA component
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Rgba {
     pub r: u8,
     pub g: u8,
@@ -231,6 +232,75 @@ pub fn argb_from_rgba(rgba: &Rgba) -> u32 {
     (rgba.a as u32) << 24 | (rgba.r as u32) << 16 | (rgba.g as u32) << 8 | rgba.b as u32
 }
 
+/// A color in ARGB format, ex. 0xff4285f4.
+///
+/// Wraps the `u32` used throughout the library so that a color can't be
+/// mistaken for another integer. Formats and parses as an #aarrggbb hex
+/// string, see `HexFormat::Android`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Argb(pub u32);
+
+impl Argb {
+    /// Creates an opaque color from RGB components.
+    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Argb {
+        Argb(argb_from_rgb(red, green, blue))
+    }
+
+    /// Returns the alpha component of the color.
+    pub fn alpha(self) -> u8 {
+        alpha_from_argb(self.0)
+    }
+
+    /// Returns the red component of the color.
+    pub fn red(self) -> u8 {
+        red_from_argb(self.0)
+    }
+
+    /// Returns the green component of the color.
+    pub fn green(self) -> u8 {
+        green_from_argb(self.0)
+    }
+
+    /// Returns the blue component of the color.
+    pub fn blue(self) -> u8 {
+        blue_from_argb(self.0)
+    }
+
+    /// Returns whether the color is opaque.
+    pub fn is_opaque(self) -> bool {
+        is_opaque(self.0)
+    }
+
+    /// Returns the same color with its alpha component replaced.
+    pub fn with_alpha(self, alpha: u8) -> Argb {
+        Argb((alpha as u32) << 24 | self.0 & 0x00ffffff)
+    }
+}
+
+impl From<u32> for Argb {
+    fn from(argb: u32) -> Argb {
+        Argb(argb)
+    }
+}
+
+impl From<Argb> for u32 {
+    fn from(argb: Argb) -> u32 {
+        argb.0
+    }
+}
+
+impl From<Rgba> for Argb {
+    fn from(rgba: Rgba) -> Argb {
+        Argb(argb_from_rgba(&rgba))
+    }
+}
+
+impl From<Argb> for Rgba {
+    fn from(argb: Argb) -> Rgba {
+        rgba_from_argb(argb.0)
+    }
+}
+
 fn lab_f(t: f64) -> f64 {
     const E: f64 = 216.0 / 24389.0;
     const KAPPA: f64 = 24389.0 / 27.0;
@@ -0,0 +1,1047 @@
+// Utility methods for CSS Color Module Level 4 representations of colors.
+use super::{
+    math_utils::{matrix_multiply, sanitize_degrees_double},
+    string_utils::{
+        try_argb_from_hex_with_format, ColorParseError, ColorParseErrorKind, HexFormat,
+    },
+};
+
+/// Named colors, sorted by name so they can be binary searched.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+// Conversion matrices from CSS Color Module Level 4, sample code for color
+// conversions. Linear RGB spaces convert to XYZ with their own white point,
+// D65 except for ProPhoto RGB, which uses D50.
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [12831.0 / 3959.0, -329.0 / 214.0, -1974.0 / 3959.0],
+    [
+        -851781.0 / 878810.0,
+        1648619.0 / 878810.0,
+        36519.0 / 878810.0,
+    ],
+    [705.0 / 12673.0, -2585.0 / 12673.0, 705.0 / 667.0],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ: [[f64; 3]; 3] = [
+    [
+        608311.0 / 1250200.0,
+        189793.0 / 714400.0,
+        198249.0 / 1000160.0,
+    ],
+    [
+        35783.0 / 156275.0,
+        247089.0 / 357200.0,
+        198249.0 / 2500400.0,
+    ],
+    [0.0, 32229.0 / 714400.0, 5220557.0 / 5000800.0],
+];
+
+const LINEAR_A98_RGB_TO_XYZ: [[f64; 3]; 3] = [
+    [
+        573536.0 / 994567.0,
+        263643.0 / 1420810.0,
+        187206.0 / 994567.0,
+    ],
+    [
+        591459.0 / 1989134.0,
+        6239551.0 / 9945670.0,
+        374412.0 / 4972835.0,
+    ],
+    [
+        53769.0 / 1989134.0,
+        351524.0 / 4972835.0,
+        4929758.0 / 4972835.0,
+    ],
+];
+
+const LINEAR_PROPHOTO_RGB_TO_XYZ_D50: [[f64; 3]; 3] = [
+    [
+        0.7977666449006423,
+        0.13518129740053308,
+        0.031347734128392286,
+    ],
+    [0.2880748288194013, 0.711835234241873, 0.00008993693872564],
+    [0.0, 0.0, 0.8251046025104602],
+];
+
+const LINEAR_REC2020_TO_XYZ: [[f64; 3]; 3] = [
+    [
+        63426534.0 / 99577255.0,
+        20160776.0 / 139408157.0,
+        47086771.0 / 278816314.0,
+    ],
+    [
+        26158966.0 / 99577255.0,
+        472592308.0 / 697040785.0,
+        8267143.0 / 139408157.0,
+    ],
+    [0.0, 19567812.0 / 697040785.0, 295819943.0 / 278816314.0],
+];
+
+/// Bradford chromatic adaptation from a D50 to a D65 white point.
+const XYZ_D50_TO_XYZ_D65: [[f64; 3]; 3] = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+const WHITE_POINT_D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+/// The largest OKLab distance between a gamut mapped color and the color it
+/// was mapped from that is not noticeable, a just noticeable difference.
+const GAMUT_MAPPING_JND: f64 = 0.02;
+
+/// Chroma precision of gamut mapping.
+const GAMUT_MAPPING_EPSILON: f64 = 0.0001;
+
+/// Returns the ARGB representation of a CSS color.
+///
+/// Accepts every color syntax of CSS Color Module Level 4: hex colors with 3,
+/// 4, 6 or 8 digits, named colors and `transparent`, and the `rgb()`,
+/// `rgba()`, `hsl()`, `hsla()`, `hwb()`, `lab()`, `lch()`, `oklab()`,
+/// `oklch()` and `color()` functions, with any of the predefined color spaces
+/// of `color()`. Colors outside of the sRGB gamut, ex.
+/// `color(display-p3 0 1 0)`, are gamut mapped the way CSS specifies: chroma
+/// is reduced in OKLCH until the color fits.
+///
+/// # Arguments
+///
+/// * `css` - A CSS color, ex. `rebeccapurple` or `oklch(70% 0.1 250)`.
+///   Letter case and surrounding whitespace are ignored.
+///
+/// # Returns
+///
+/// ARGB representation of the color, including its alpha, or the position and
+/// reason of the first problem found in `css`.
+pub fn argb_from_css(css: &str) -> Result<u32, ColorParseError> {
+    let trimmed = css.trim_start();
+    let mut parser = Parser {
+        input: trimmed.trim_end(),
+        offset: css.len() - trimmed.len(),
+        position: 0,
+    };
+    let argb = parser.color()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(argb),
+        Some(c) => Err(parser.error(parser.position, ColorParseErrorKind::Unexpected(c.into()))),
+    }
+}
+
+/// A component of a color function.
+#[derive(Clone, Copy)]
+enum Value {
+    Number(f64),
+    Percentage(f64),
+    /// An angle with a unit, in degrees.
+    Angle(f64),
+    /// The `none` keyword, a missing component.
+    None,
+}
+
+#[derive(Clone, Copy)]
+struct Component {
+    value: Value,
+    position: usize,
+    length: usize,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    /// Position of `input` in the string given to `argb_from_css`.
+    offset: usize,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, position: usize, kind: ColorParseErrorKind) -> ColorParseError {
+        ColorParseError {
+            position: self.offset + position,
+            kind,
+        }
+    }
+
+    fn unexpected_here(&self) -> ColorParseError {
+        match self.peek() {
+            Some(c) => self.error(self.position, ColorParseErrorKind::Unexpected(c.into())),
+            None => self.error(self.position, ColorParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `c` if it is next, after any whitespace.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ColorParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected_here())
+        }
+    }
+
+    /// Consumes an identifier, lowercased.
+    fn ident(&mut self) -> Option<String> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '-') {
+            return None;
+        }
+        self.position += length;
+        Some(rest[..length].to_ascii_lowercase())
+    }
+
+    fn color(&mut self) -> Result<u32, ColorParseError> {
+        let start = self.position;
+        if self.eat('#') {
+            return self.hex();
+        }
+        let name = self.ident().ok_or_else(|| self.unexpected_here())?;
+        if !self.eat('(') {
+            return named_color(&name)
+                .ok_or_else(|| self.error(start, ColorParseErrorKind::UnknownColor(name)));
+        }
+
+        let (linear_srgb, alpha) = match name.as_str() {
+            "rgb" | "rgba" => {
+                let (components, alpha) = self.arguments(true)?;
+                let mut encoded = [0.0; 3];
+                for (channel, component) in encoded.iter_mut().zip(components) {
+                    *channel =
+                        (self.number_or_percentage(component, 255.0)? / 255.0).clamp(0.0, 1.0);
+                }
+                (encoded.map(linearized_srgb), alpha)
+            }
+            "hsl" | "hsla" => {
+                let ([hue, saturation, lightness], alpha) = self.arguments(true)?;
+                let hue = self.hue(hue)?;
+                let saturation = self.number_or_percentage(saturation, 100.0)? / 100.0;
+                let lightness = self.number_or_percentage(lightness, 100.0)? / 100.0;
+                let encoded = srgb_from_hsl(hue, saturation.max(0.0), lightness.clamp(0.0, 1.0));
+                (encoded.map(linearized_srgb), alpha)
+            }
+            "hwb" => {
+                let ([hue, whiteness, blackness], alpha) = self.arguments(false)?;
+                let hue = self.hue(hue)?;
+                let whiteness = self.number_or_percentage(whiteness, 100.0)? / 100.0;
+                let blackness = self.number_or_percentage(blackness, 100.0)? / 100.0;
+                let encoded =
+                    srgb_from_hwb(hue, whiteness.clamp(0.0, 1.0), blackness.clamp(0.0, 1.0));
+                (encoded.map(linearized_srgb), alpha)
+            }
+            "lab" => {
+                let ([l, a, b], alpha) = self.arguments(false)?;
+                let lab = [
+                    self.number_or_percentage(l, 100.0)?.clamp(0.0, 100.0),
+                    self.number_or_percentage(a, 125.0)?,
+                    self.number_or_percentage(b, 125.0)?,
+                ];
+                (linear_srgb_from_lab(lab), alpha)
+            }
+            "lch" => {
+                let ([l, c, h], alpha) = self.arguments(false)?;
+                let l = self.number_or_percentage(l, 100.0)?.clamp(0.0, 100.0);
+                let c = self.number_or_percentage(c, 150.0)?.max(0.0);
+                let h = self.hue(h)?.to_radians();
+                (linear_srgb_from_lab([l, c * h.cos(), c * h.sin()]), alpha)
+            }
+            "oklab" => {
+                let ([l, a, b], alpha) = self.arguments(false)?;
+                let oklab = [
+                    self.number_or_percentage(l, 1.0)?.clamp(0.0, 1.0),
+                    self.number_or_percentage(a, 0.4)?,
+                    self.number_or_percentage(b, 0.4)?,
+                ];
+                (linear_srgb_from_oklab(oklab), alpha)
+            }
+            "oklch" => {
+                let ([l, c, h], alpha) = self.arguments(false)?;
+                let l = self.number_or_percentage(l, 1.0)?.clamp(0.0, 1.0);
+                let c = self.number_or_percentage(c, 0.4)?.max(0.0);
+                let h = self.hue(h)?.to_radians();
+                (linear_srgb_from_oklab([l, c * h.cos(), c * h.sin()]), alpha)
+            }
+            "color" => {
+                self.skip_whitespace();
+                let space_start = self.position;
+                let space = self.ident().ok_or_else(|| self.unexpected_here())?;
+                let (components, alpha) = self.arguments(false)?;
+                let mut rgb_or_xyz = [0.0; 3];
+                for (channel, component) in rgb_or_xyz.iter_mut().zip(components) {
+                    *channel = self.number_or_percentage(component, 1.0)?;
+                }
+                let linear_srgb =
+                    linear_srgb_from_color_space(&space, rgb_or_xyz).ok_or_else(|| {
+                        self.error(space_start, ColorParseErrorKind::UnknownColor(space))
+                    })?;
+                (linear_srgb, alpha)
+            }
+            _ => return Err(self.error(start, ColorParseErrorKind::UnknownColor(name))),
+        };
+
+        let alpha = match alpha {
+            Some(alpha) => self.number_or_percentage(alpha, 1.0)?.clamp(0.0, 1.0),
+            None => 1.0,
+        };
+        Ok(argb_from_linear_srgb(gamut_mapped(linear_srgb), alpha))
+    }
+
+    /// Parses a hex color after its `#`.
+    fn hex(&mut self) -> Result<u32, ColorParseError> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let argb = try_argb_from_hex_with_format(&rest[..length], HexFormat::Css)
+            .map_err(|error| self.error(self.position + error.position, error.kind))?;
+        self.position += length;
+        Ok(argb)
+    }
+
+    /// Parses the three components and optional alpha of a color function,
+    /// and its closing parenthesis. Components are separated by whitespace,
+    /// with `/` before alpha, or with commas if `legacy` syntax is allowed.
+    fn arguments(
+        &mut self,
+        legacy: bool,
+    ) -> Result<([Component; 3], Option<Component>), ColorParseError> {
+        let first = self.component()?;
+        let commas = legacy && self.eat(',');
+        let second = self.component()?;
+        if commas {
+            self.expect(',')?;
+        }
+        let third = self.component()?;
+        let alpha_separator = if commas { ',' } else { '/' };
+        let alpha = if self.eat(alpha_separator) {
+            Some(self.component()?)
+        } else {
+            None
+        };
+        self.expect(')')?;
+        Ok(([first, second, third], alpha))
+    }
+
+    /// Parses a number, percentage, angle or `none`.
+    fn component(&mut self) -> Result<Component, ColorParseError> {
+        self.skip_whitespace();
+        let position = self.position;
+        let rest = self.rest();
+        let mut chars = rest.chars();
+        let starts_number = match chars.next() {
+            Some(c) if c.is_ascii_digit() || c == '.' => true,
+            Some('+') | Some('-') => chars.next().is_some_and(|c| c.is_ascii_digit() || c == '.'),
+            _ => false,
+        };
+
+        if !starts_number {
+            return match self.ident() {
+                Some(ident) if ident == "none" => Ok(Component {
+                    value: Value::None,
+                    position,
+                    length: ident.len(),
+                }),
+                Some(ident) => Err(self.error(position, ColorParseErrorKind::Unexpected(ident))),
+                None => Err(self.unexpected_here()),
+            };
+        }
+
+        let length = number_length(rest);
+        let number: f64 = rest[..length].parse().map_err(|_| {
+            self.error(
+                position,
+                ColorParseErrorKind::Unexpected(rest[..length].into()),
+            )
+        })?;
+        self.position += length;
+
+        let value = if self.rest().starts_with('%') {
+            self.position += 1;
+            Value::Percentage(number)
+        } else if let Some(unit) = self.ident() {
+            let degrees = match unit.as_str() {
+                "deg" => number,
+                "rad" => number.to_degrees(),
+                "grad" => number * 0.9,
+                "turn" => number * 360.0,
+                _ => {
+                    return Err(self.error(position + length, ColorParseErrorKind::Unexpected(unit)))
+                }
+            };
+            Value::Angle(degrees)
+        } else {
+            Value::Number(number)
+        };
+        Ok(Component {
+            value,
+            position,
+            length: self.position - position,
+        })
+    }
+
+    fn unexpected_component(&self, component: Component) -> ColorParseError {
+        let text = &self.input[component.position..component.position + component.length];
+        self.error(
+            component.position,
+            ColorParseErrorKind::Unexpected(text.into()),
+        )
+    }
+
+    /// Resolves a number, or a percentage of `reference`.
+    fn number_or_percentage(
+        &self,
+        component: Component,
+        reference: f64,
+    ) -> Result<f64, ColorParseError> {
+        match component.value {
+            Value::Number(number) => Ok(number),
+            Value::Percentage(percentage) => Ok(percentage / 100.0 * reference),
+            Value::None => Ok(0.0),
+            Value::Angle(_) => Err(self.unexpected_component(component)),
+        }
+    }
+
+    /// Resolves a hue, in degrees.
+    fn hue(&self, component: Component) -> Result<f64, ColorParseError> {
+        match component.value {
+            Value::Number(degrees) | Value::Angle(degrees) => Ok(sanitize_degrees_double(degrees)),
+            Value::None => Ok(0.0),
+            Value::Percentage(_) => Err(self.unexpected_component(component)),
+        }
+    }
+}
+
+/// The length of the number at the start of `text`: a sign, digits with an
+/// optional decimal point, and an optional exponent.
+fn number_length(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let digits_from = |mut index: usize| {
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        index
+    };
+    let mut end = if matches!(bytes[0], b'+' | b'-') {
+        1
+    } else {
+        0
+    };
+    end = digits_from(end);
+    if end < bytes.len() && bytes[end] == b'.' {
+        end = digits_from(end + 1);
+    }
+    if end < bytes.len() && matches!(bytes[end], b'e' | b'E') {
+        let mut exponent = end + 1;
+        if exponent < bytes.len() && matches!(bytes[exponent], b'+' | b'-') {
+            exponent += 1;
+        }
+        if exponent < bytes.len() && bytes[exponent].is_ascii_digit() {
+            end = digits_from(exponent);
+        }
+    }
+    end
+}
+
+fn named_color(name: &str) -> Option<u32> {
+    if name == "transparent" {
+        return Some(0x00000000);
+    }
+    NAMED_COLORS
+        .binary_search_by_key(&name, |&(named, _)| named)
+        .ok()
+        .map(|index| 0xff000000 | NAMED_COLORS[index].1)
+}
+
+/// Converts HSL to gamma encoded sRGB, from 0 to 1.
+fn srgb_from_hsl(hue: f64, saturation: f64, lightness: f64) -> [f64; 3] {
+    let channel = |n: f64| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    [channel(0.0), channel(8.0), channel(4.0)]
+}
+
+/// Converts HWB to gamma encoded sRGB, from 0 to 1.
+fn srgb_from_hwb(hue: f64, whiteness: f64, blackness: f64) -> [f64; 3] {
+    if whiteness + blackness >= 1.0 {
+        let gray = whiteness / (whiteness + blackness);
+        return [gray; 3];
+    }
+    srgb_from_hsl(hue, 1.0, 0.5).map(|channel| channel * (1.0 - whiteness - blackness) + whiteness)
+}
+
+/// Converts a gamma encoded sRGB or Display P3 channel to linear light,
+/// extended to values outside of 0 to 1.
+fn linearized_srgb(channel: f64) -> f64 {
+    let magnitude = channel.abs();
+    if magnitude <= 0.04045 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((magnitude + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear sRGB channel to gamma encoding, extended to values
+/// outside of 0 to 1.
+fn delinearized_srgb(channel: f64) -> f64 {
+    let magnitude = channel.abs();
+    if magnitude > 0.0031308 {
+        channel.signum() * (1.055 * magnitude.powf(1.0 / 2.4) - 0.055)
+    } else {
+        channel * 12.92
+    }
+}
+
+/// Converts the channels of a predefined color space of `color()` to linear
+/// sRGB, or returns `None` if the color space is not known.
+fn linear_srgb_from_color_space(space: &str, channels: [f64; 3]) -> Option<[f64; 3]> {
+    let xyz_d65 = match space {
+        "srgb" => return Some(channels.map(linearized_srgb)),
+        "srgb-linear" => return Some(channels),
+        "display-p3" => matrix_multiply(&channels.map(linearized_srgb), &LINEAR_DISPLAY_P3_TO_XYZ),
+        "a98-rgb" => {
+            let linear =
+                channels.map(|channel| channel.signum() * channel.abs().powf(563.0 / 256.0));
+            matrix_multiply(&linear, &LINEAR_A98_RGB_TO_XYZ)
+        }
+        "prophoto-rgb" => {
+            let linear = channels.map(|channel| {
+                if channel.abs() <= 16.0 / 512.0 {
+                    channel / 16.0
+                } else {
+                    channel.signum() * channel.abs().powf(1.8)
+                }
+            });
+            let xyz_d50 = matrix_multiply(&linear, &LINEAR_PROPHOTO_RGB_TO_XYZ_D50);
+            matrix_multiply(&xyz_d50, &XYZ_D50_TO_XYZ_D65)
+        }
+        "rec2020" => {
+            const ALPHA: f64 = 1.09929682680944;
+            const BETA: f64 = 0.018053968510807;
+            let linear = channels.map(|channel| {
+                if channel.abs() < BETA * 4.5 {
+                    channel / 4.5
+                } else {
+                    channel.signum() * ((channel.abs() + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45)
+                }
+            });
+            matrix_multiply(&linear, &LINEAR_REC2020_TO_XYZ)
+        }
+        "xyz" | "xyz-d65" => channels,
+        "xyz-d50" => matrix_multiply(&channels, &XYZ_D50_TO_XYZ_D65),
+        _ => return None,
+    };
+    Some(matrix_multiply(&xyz_d65, &XYZ_TO_LINEAR_SRGB))
+}
+
+/// Converts CIE L*a*b*, with a D50 white point as CSS uses, to linear sRGB.
+fn linear_srgb_from_lab(lab: [f64; 3]) -> [f64; 3] {
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const EPSILON: f64 = 216.0 / 24389.0;
+    let [l, a, b] = lab;
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+    let inverse_f = |f: f64| {
+        if f.powi(3) > EPSILON {
+            f.powi(3)
+        } else {
+            (116.0 * f - 16.0) / KAPPA
+        }
+    };
+    let y = if l > KAPPA * EPSILON {
+        fy.powi(3)
+    } else {
+        l / KAPPA
+    };
+    let xyz_d50 = [
+        inverse_f(fx) * WHITE_POINT_D50[0],
+        y * WHITE_POINT_D50[1],
+        inverse_f(fz) * WHITE_POINT_D50[2],
+    ];
+    let xyz_d65 = matrix_multiply(&xyz_d50, &XYZ_D50_TO_XYZ_D65);
+    matrix_multiply(&xyz_d65, &XYZ_TO_LINEAR_SRGB)
+}
+
+fn linear_srgb_from_oklab(oklab: [f64; 3]) -> [f64; 3] {
+    let [l, a, b] = oklab;
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+}
+
+fn oklab_from_linear_srgb(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb;
+    let l_ = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m_ = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s_ = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_,
+        1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_,
+        0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_,
+    ]
+}
+
+fn is_in_srgb_gamut(rgb: [f64; 3]) -> bool {
+    rgb.iter().all(|channel| (0.0..=1.0).contains(channel))
+}
+
+/// The OKLab distance between two linear sRGB colors.
+fn delta_e_ok(one: [f64; 3], two: [f64; 3]) -> f64 {
+    let one = oklab_from_linear_srgb(one);
+    let two = oklab_from_linear_srgb(two);
+    ((one[0] - two[0]).powi(2) + (one[1] - two[1]).powi(2) + (one[2] - two[2]).powi(2)).sqrt()
+}
+
+/// Maps a linear sRGB color into the sRGB gamut with the CSS Color Module
+/// Level 4 gamut mapping algorithm: lightness and hue are kept in OKLCH,
+/// and chroma is reduced until clipping the color is not noticeable.
+fn gamut_mapped(rgb: [f64; 3]) -> [f64; 3] {
+    if is_in_srgb_gamut(rgb) {
+        return rgb;
+    }
+    let [lightness, a, b] = oklab_from_linear_srgb(rgb);
+    if lightness >= 1.0 {
+        return [1.0; 3];
+    }
+    if lightness <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let hue = b.atan2(a);
+    let with_chroma =
+        |chroma: f64| linear_srgb_from_oklab([lightness, chroma * hue.cos(), chroma * hue.sin()]);
+    let clip = |rgb: [f64; 3]| rgb.map(|channel| channel.clamp(0.0, 1.0));
+
+    let mut clipped = clip(rgb);
+    if delta_e_ok(clipped, rgb) < GAMUT_MAPPING_JND {
+        return clipped;
+    }
+    let mut min = 0.0;
+    let mut max = a.hypot(b);
+    let mut min_in_gamut = true;
+    while max - min > GAMUT_MAPPING_EPSILON {
+        let chroma = (min + max) / 2.0;
+        let current = with_chroma(chroma);
+        if min_in_gamut && is_in_srgb_gamut(current) {
+            min = chroma;
+            continue;
+        }
+        clipped = clip(current);
+        let error = delta_e_ok(clipped, current);
+        if error < GAMUT_MAPPING_JND {
+            if GAMUT_MAPPING_JND - error < GAMUT_MAPPING_EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
+
+fn argb_from_linear_srgb(rgb: [f64; 3], alpha: f64) -> u32 {
+    let [r, g, b] = rgb.map(|channel| {
+        (delinearized_srgb(channel) * 255.0)
+            .round()
+            .clamp(0.0, 255.0) as u32
+    });
+    let a = (alpha * 255.0).round() as u32;
+    (a << 24) | (r << 16) | (g << 8) | b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(position: usize, kind: ColorParseErrorKind) -> Result<u32, ColorParseError> {
+        Err(ColorParseError { position, kind })
+    }
+
+    /// Asserts each channel of `css` is within 1 of `expected`, for values
+    /// written with only a few decimals.
+    fn assert_near(css: &str, expected: u32) {
+        let argb = argb_from_css(css).unwrap();
+        for shift in [24, 16, 8, 0] {
+            let channel = |argb: u32| ((argb >> shift) & 0xff) as i32;
+            assert!(
+                (channel(argb) - channel(expected)).abs() <= 1,
+                "{css} is {argb:08x}, expected {expected:08x}"
+            );
+        }
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(argb_from_css("rebeccapurple"), Ok(0xff663399));
+        assert_eq!(argb_from_css("  RebeccaPurple "), Ok(0xff663399));
+        assert_eq!(argb_from_css("aliceblue"), Ok(0xfff0f8ff));
+        assert_eq!(argb_from_css("yellowgreen"), Ok(0xff9acd32));
+        assert_eq!(argb_from_css("transparent"), Ok(0x00000000));
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(argb_from_css("#f80"), Ok(0xffff8800));
+        assert_eq!(argb_from_css("#f808"), Ok(0x88ff8800));
+        assert_eq!(argb_from_css("#FF8800"), Ok(0xffff8800));
+        assert_eq!(argb_from_css("#ff880080"), Ok(0x80ff8800));
+    }
+
+    #[test]
+    fn rgb_legacy_and_modern_syntax() {
+        assert_eq!(argb_from_css("rgb(255, 136, 0)"), Ok(0xffff8800));
+        assert_eq!(argb_from_css("rgba(255, 136, 0, 0.5)"), Ok(0x80ff8800));
+        assert_eq!(argb_from_css("rgb(255 136 0)"), Ok(0xffff8800));
+        assert_eq!(argb_from_css("rgb(255 136 0 / 50%)"), Ok(0x80ff8800));
+        assert_eq!(argb_from_css("rgba(100% 0% 0%)"), Ok(0xffff0000));
+        assert_eq!(argb_from_css("rgb(300 -20 0 / 2)"), Ok(0xffff0000));
+        assert_eq!(argb_from_css("rgb(none 136 none / none)"), Ok(0x00008800));
+        assert_eq!(argb_from_css("hsl(120, 100%, 50%)"), Ok(0xff00ff00));
+        assert_eq!(argb_from_css("hsla(120, 100%, 25%, 0.2)"), Ok(0x33008000));
+        assert_eq!(argb_from_css("hsl(none 100% 50%)"), Ok(0xffff0000));
+        assert_eq!(argb_from_css("hwb(0 0% 0%)"), Ok(0xffff0000));
+        assert_eq!(argb_from_css("hwb(0 60% 60%)"), Ok(0xff808080));
+        // Commas and whitespace can't be mixed, and hwb() has no legacy syntax.
+        assert_eq!(
+            argb_from_css("rgb(255, 136 0)"),
+            error(13, ColorParseErrorKind::Unexpected("0".into()))
+        );
+        assert_eq!(
+            argb_from_css("rgb(255 136 0, 0.5)"),
+            error(13, ColorParseErrorKind::Unexpected(",".into()))
+        );
+        assert_eq!(
+            argb_from_css("hwb(0, 0%, 0%)"),
+            error(5, ColorParseErrorKind::Unexpected(",".into()))
+        );
+    }
+
+    #[test]
+    fn angle_units() {
+        for hue in [
+            "120",
+            "120deg",
+            "2.0943951rad",
+            "133.33333grad",
+            "0.33333333turn",
+            "-240deg",
+        ] {
+            assert_eq!(
+                argb_from_css(&format!("hsl({} 100% 50%)", hue)),
+                Ok(0xff00ff00),
+                "{hue}"
+            );
+        }
+        assert_eq!(
+            argb_from_css("hsl(120foo 100% 50%)"),
+            error(7, ColorParseErrorKind::Unexpected("foo".into()))
+        );
+        assert_eq!(
+            argb_from_css("rgb(120deg 0 0)"),
+            error(4, ColorParseErrorKind::Unexpected("120deg".into()))
+        );
+    }
+
+    #[test]
+    fn lab_lch_oklab_oklch() {
+        // sRGB red and white, from the CSS Color Module Level 4 examples.
+        assert_near("lab(54.29% 80.8 69.89)", 0xffff0000);
+        assert_near("lch(54.29% 106.84 40.85)", 0xffff0000);
+        assert_near("oklab(62.8% 0.2249 0.1258)", 0xffff0000);
+        assert_near("oklch(0.628 0.2577 29.23)", 0xffff0000);
+        assert_near("oklch(62.8% 64.4% 29.23deg / 0.5)", 0x80ff0000);
+        assert_eq!(argb_from_css("lab(100 0 0)"), Ok(0xffffffff));
+        assert_eq!(argb_from_css("lch(0% 0 none)"), Ok(0xff000000));
+        assert_eq!(argb_from_css("oklab(1 0 0)"), Ok(0xffffffff));
+        assert_eq!(argb_from_css("oklch(0 0 0)"), Ok(0xff000000));
+    }
+
+    #[test]
+    fn color_function_spaces() {
+        // sRGB red, written in each predefined color space.
+        for css in [
+            "color(srgb 1 0 0)",
+            "color(srgb 100% 0% 0%)",
+            "color(srgb-linear 1 0 0)",
+            "color(display-p3 0.9175 0.2003 0.1386)",
+            "color(a98-rgb 0.8586 0 0)",
+            "color(prophoto-rgb 0.7023 0.2757 0.1036)",
+            "color(rec2020 0.792 0.231 0.0738)",
+            "color(xyz 0.4124 0.2126 0.0193)",
+            "color(xyz-d65 0.4124 0.2126 0.0193)",
+            "color(xyz-d50 0.4360 0.2225 0.0139)",
+        ] {
+            assert_near(css, 0xffff0000);
+        }
+        assert_eq!(
+            argb_from_css("color(srgb-linear 0.2158605 0.2158605 0.2158605)"),
+            Ok(0xff808080)
+        );
+        assert_eq!(argb_from_css("color(srgb 1 0 0 / 0.5)"), Ok(0x80ff0000));
+        assert_eq!(
+            argb_from_css("color(unknown 1 0 0)"),
+            error(6, ColorParseErrorKind::UnknownColor("unknown".into()))
+        );
+    }
+
+    #[test]
+    fn out_of_gamut_colors_are_mapped_by_chroma() {
+        let p3_green = linear_srgb_from_color_space("display-p3", [0.0, 1.0, 0.0]).unwrap();
+        assert!(!is_in_srgb_gamut(p3_green));
+
+        let argb = argb_from_css("color(display-p3 0 1 0)").unwrap();
+
+        // Clipping each channel would give 0xff00ff00. Mapping instead keeps
+        // lightness and hue in OKLCH, reduces chroma, and then clips no more
+        // than a just noticeable difference.
+        assert_ne!(argb, 0xff00ff00);
+        let channel = |shift: u32| linearized_srgb(((argb >> shift) & 0xff) as f64 / 255.0);
+        let mapped = oklab_from_linear_srgb([channel(16), channel(8), channel(0)]);
+        let original = oklab_from_linear_srgb(p3_green);
+        let chroma = mapped[1].hypot(mapped[2]);
+        assert!(chroma < original[1].hypot(original[2]));
+        let hue = original[2].atan2(original[1]);
+        let reduced = [original[0], chroma * hue.cos(), chroma * hue.sin()];
+        let distance = (0..3)
+            .map(|i| (mapped[i] - reduced[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(
+            distance < GAMUT_MAPPING_JND,
+            "{argb:08x} is {distance} away"
+        );
+    }
+
+    #[test]
+    fn errors_report_their_position() {
+        assert_eq!(
+            argb_from_css("notacolor"),
+            error(0, ColorParseErrorKind::UnknownColor("notacolor".into()))
+        );
+        assert_eq!(
+            argb_from_css("  spin(1 2 3)"),
+            error(2, ColorParseErrorKind::UnknownColor("spin".into()))
+        );
+        assert_eq!(
+            argb_from_css(" #12345"),
+            error(2, ColorParseErrorKind::InvalidLength(5))
+        );
+        assert_eq!(
+            argb_from_css("#12x456"),
+            error(3, ColorParseErrorKind::InvalidDigit('x'))
+        );
+        assert_eq!(
+            argb_from_css("rgb(255 0 0 0)"),
+            error(12, ColorParseErrorKind::Unexpected("0".into()))
+        );
+        assert_eq!(
+            argb_from_css("rgb(255 0"),
+            error(9, ColorParseErrorKind::UnexpectedEnd)
+        );
+        assert_eq!(
+            argb_from_css("hsl(120% 50% 50%)"),
+            error(4, ColorParseErrorKind::Unexpected("120%".into()))
+        );
+        assert_eq!(
+            argb_from_css("rgb(1 2 three)"),
+            error(8, ColorParseErrorKind::Unexpected("three".into()))
+        );
+        assert_eq!(
+            argb_from_css("red blue"),
+            error(4, ColorParseErrorKind::Unexpected("b".into()))
+        );
+        assert_eq!(
+            argb_from_css(""),
+            error(0, ColorParseErrorKind::UnexpectedEnd)
+        );
+    }
+}
@@ -0,0 +1,908 @@
+// Utility methods for extracting theme colors from images.
+use std::collections::HashMap;
+#[cfg(feature = "image")]
+use std::{fmt, fs, path::Path};
+
+use crate::{
+    quantize::{PointProvider, PointProviderLab, QuantizerCelebi},
+    score::Score,
+};
+
+use super::color_utils::{
+    alpha_from_argb, argb_from_linrgb, blue_from_argb, green_from_argb, linearized, red_from_argb,
+};
+#[cfg(feature = "image")]
+use super::color_utils::{argb_from_rgba, Rgba};
+
+/// The most pixels used to extract colors from an image by default. Larger
+/// images are downscaled first, which keeps extraction fast without changing
+/// which colors are dominant.
+pub const MAX_EXTRACTION_PIXELS: usize = 112 * 112;
+
+/// The most extra times a pixel counts because of center weighting, see
+/// `SamplingOptions::center_weight`. Weighted pixels are repeated, so this
+/// bounds the memory sampling uses.
+pub const MAX_CENTER_WEIGHT: f64 = 15.0;
+
+/// The number of colors an image is quantized to before scoring.
+const QUANTIZE_MAX_COLORS: usize = 128;
+
+/// An image as rows of ARGB pixels, top to bottom and left to right.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgbImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+impl ArgbImage {
+    /// Creates an image from ARGB pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width of the image in pixels.
+    /// * `height` - Height of the image in pixels.
+    /// * `pixels` - `width * height` colors in ARGB format, row by row.
+    pub fn new(width: usize, height: usize, pixels: Vec<u32>) -> ArgbImage {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "expected {}x{} pixels",
+            width,
+            height
+        );
+        ArgbImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Decodes a PNG or JPEG file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the image file.
+    #[cfg(feature = "image")]
+    pub fn open(path: impl AsRef<Path>) -> Result<ArgbImage, ImageError> {
+        ArgbImage::decode(&fs::read(path)?)
+    }
+
+    /// Decodes a PNG or JPEG image held in memory. The format is detected
+    /// from the data, not from a file extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Contents of a PNG or JPEG file.
+    #[cfg(feature = "image")]
+    pub fn decode(bytes: &[u8]) -> Result<ArgbImage, ImageError> {
+        if bytes.starts_with(&PNG_SIGNATURE) {
+            decode_png(bytes)
+        } else if bytes.starts_with(&JPEG_SIGNATURE) {
+            decode_jpeg(bytes)
+        } else {
+            Err(ImageError::UnsupportedFormat)
+        }
+    }
+
+    /// Samples the pixels of the image to quantize, see `SamplingOptions`.
+    ///
+    /// Sampling is deterministic: the same image and options always produce
+    /// the same pixels, in the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - How to downscale, filter and weight the pixels.
+    ///
+    /// # Returns
+    ///
+    /// Opaque colors in ARGB format. Pixels may repeat when center weighting
+    /// is used.
+    pub fn sample(&self, options: &SamplingOptions) -> Vec<u32> {
+        self.weighted_samples(None, options)
+            .into_iter()
+            .flat_map(|sample| std::iter::repeat_n(sample.argb, sample.copies))
+            .collect()
+    }
+
+    /// Samples the pixels of the image like `sample`, then quantizes them and
+    /// counts how much of the image each color covers, in proportion to the
+    /// weights of `mask`. Pixels with a weight of 0 are ignored entirely.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - Weight of each pixel, the same size as the image.
+    /// * `options` - How to downscale, filter and weight the pixels.
+    ///
+    /// # Returns
+    ///
+    /// A map with keys of colors in ARGB format, and values of their weighted
+    /// population, suitable for `Score::score`.
+    pub fn weighted_population(
+        &self,
+        mask: &WeightMask,
+        options: &SamplingOptions,
+    ) -> HashMap<u32, u32> {
+        assert!(
+            mask.width == self.width && mask.height == self.height,
+            "expected a {}x{} mask",
+            self.width,
+            self.height
+        );
+        let samples = self.weighted_samples(Some(mask), options);
+        let pixels: Vec<u32> = samples
+            .iter()
+            .flat_map(|sample| std::iter::repeat_n(sample.argb, sample.copies))
+            .collect();
+        // Sorted, so that ties between clusters resolve the same way every time.
+        let mut clusters: Vec<u32> = QuantizerCelebi::quantize(&pixels, QUANTIZE_MAX_COLORS)
+            .into_keys()
+            .collect();
+        clusters.sort_unstable();
+
+        let point_provider = PointProviderLab;
+        let cluster_points: Vec<[f64; 3]> = clusters
+            .iter()
+            .map(|&argb| point_provider.point_from_argb(argb))
+            .collect();
+        let mut nearest_clusters: HashMap<u32, u32> = HashMap::new();
+        let mut population: HashMap<u32, u64> = HashMap::new();
+        for sample in &samples {
+            let cluster = *nearest_clusters.entry(sample.argb).or_insert_with(|| {
+                let point = point_provider.point_from_argb(sample.argb);
+                let mut nearest = 0;
+                let mut nearest_distance = f64::MAX;
+                for (index, cluster_point) in cluster_points.iter().enumerate() {
+                    let distance = point_provider.distance(&point, cluster_point);
+                    if distance < nearest_distance {
+                        nearest = index;
+                        nearest_distance = distance;
+                    }
+                }
+                clusters[nearest]
+            });
+            *population.entry(cluster).or_insert(0) += sample.weight as u64 * sample.copies as u64;
+        }
+        populations_to_u32(population)
+    }
+
+    /// Downscales the image and its mask, dropping pixels below the alpha
+    /// threshold and pixels without weight.
+    fn weighted_samples(
+        &self,
+        mask: Option<&WeightMask>,
+        options: &SamplingOptions,
+    ) -> Vec<WeightedSample> {
+        let (block, average) = match options.downscale {
+            Downscale::None => (1, false),
+            Downscale::Stride { step } => (step.max(1), false),
+            Downscale::AreaAverage { max_pixels } => (self.block_size(max_pixels), true),
+        };
+        let width = self.width.div_ceil(block);
+        let height = self.height.div_ceil(block);
+        let weight_at = |index: usize| mask.map_or(255, |mask| mask.weights[index] as u32);
+
+        // Linearizing is slow compared to summing, so look components up.
+        let linearized_components: Vec<f64> = if average {
+            (0..=255).map(linearized).collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut samples = Vec::with_capacity(width * height);
+        for cell_y in 0..height {
+            for cell_x in 0..width {
+                let (argb, weight) = if average {
+                    // Average the block, weighting each pixel by the mask.
+                    let mut linrgb_sum = [0.0; 3];
+                    let mut weight_sum = 0;
+                    let mut area = 0;
+                    for y in cell_y * block..((cell_y + 1) * block).min(self.height) {
+                        for x in cell_x * block..((cell_x + 1) * block).min(self.width) {
+                            area += 1;
+                            let argb = self.pixels[y * self.width + x];
+                            let weight = weight_at(y * self.width + x);
+                            if alpha_from_argb(argb) < options.alpha_threshold || weight == 0 {
+                                continue;
+                            }
+                            let components = [
+                                red_from_argb(argb),
+                                green_from_argb(argb),
+                                blue_from_argb(argb),
+                            ];
+                            for (sum, component) in linrgb_sum.iter_mut().zip(components) {
+                                *sum += linearized_components[component as usize] * weight as f64;
+                            }
+                            weight_sum += weight;
+                        }
+                    }
+                    if weight_sum == 0 {
+                        continue;
+                    }
+                    let argb = argb_from_linrgb(&linrgb_sum.map(|sum| sum / weight_sum as f64));
+                    let weight = (weight_sum as f64 / area as f64).round().max(1.0) as u32;
+                    (argb, weight)
+                } else {
+                    let index = cell_y * block * self.width + cell_x * block;
+                    let argb = self.pixels[index];
+                    let weight = weight_at(index);
+                    if alpha_from_argb(argb) < options.alpha_threshold || weight == 0 {
+                        continue;
+                    }
+                    (argb, weight)
+                };
+
+                samples.push(WeightedSample {
+                    argb: argb | 0xff000000,
+                    weight,
+                    copies: center_weight_at(cell_x, cell_y, width, height, options.center_weight),
+                });
+            }
+        }
+        samples
+    }
+
+    /// The smallest size of square blocks that, averaged, leave at most
+    /// `max_pixels` pixels.
+    fn block_size(&self, max_pixels: usize) -> usize {
+        let pixel_count = self.width * self.height;
+        if max_pixels == 0 || pixel_count <= max_pixels {
+            return 1;
+        }
+        let mut block = (pixel_count as f64 / max_pixels as f64).sqrt().ceil() as usize;
+        while self.width.div_ceil(block) * self.height.div_ceil(block) > max_pixels {
+            block += 1;
+        }
+        block
+    }
+}
+
+/// Large images can outgrow u32 populations. Scoring only uses proportions,
+/// so every population is scaled down until the largest fits.
+fn populations_to_u32(population: HashMap<u32, u64>) -> HashMap<u32, u32> {
+    let largest = population.values().copied().max().unwrap_or(0);
+    let scale = largest.div_ceil(u32::MAX as u64).max(1);
+    population
+        .into_iter()
+        .map(|(argb, population)| (argb, population.div_ceil(scale) as u32))
+        .collect()
+}
+
+/// An opaque, downscaled pixel of an image, with its weight from a mask and
+/// the number of times it counts because of center weighting.
+struct WeightedSample {
+    argb: u32,
+    weight: u32,
+    copies: usize,
+}
+
+/// How many times the pixel at `x`, `y` of a `width` by `height` image is
+/// counted: `1 + center_weight` at the center of the image, falling linearly
+/// to 1 at the corners. `center_weight` is capped at `MAX_CENTER_WEIGHT`.
+fn center_weight_at(x: usize, y: usize, width: usize, height: usize, center_weight: f64) -> usize {
+    if center_weight.is_nan() || center_weight <= 0.0 {
+        return 1;
+    }
+    let center_weight = center_weight.min(MAX_CENTER_WEIGHT);
+    let dx = (x as f64 + 0.5) / width as f64 * 2.0 - 1.0;
+    let dy = (y as f64 + 0.5) / height as f64 * 2.0 - 1.0;
+    let distance = dx.hypot(dy) / std::f64::consts::SQRT_2;
+    (1.0 + center_weight * (1.0 - distance)).round().max(1.0) as usize
+}
+
+/// A rectangle of pixels, from its top left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// How much each pixel of an image counts towards the population of its
+/// color, from 0 (not at all) to 255 (fully). Used to extract colors from
+/// part of an image, ex. album art without its letterboxing or overlaid text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeightMask {
+    pub width: usize,
+    pub height: usize,
+    pub weights: Vec<u8>,
+}
+
+impl WeightMask {
+    /// Creates a mask from per-pixel weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width of the image in pixels.
+    /// * `height` - Height of the image in pixels.
+    /// * `weights` - `width * height` weights, row by row.
+    pub fn new(width: usize, height: usize, weights: Vec<u8>) -> WeightMask {
+        assert_eq!(
+            weights.len(),
+            width * height,
+            "expected {}x{} weights",
+            width,
+            height
+        );
+        WeightMask {
+            width,
+            height,
+            weights,
+        }
+    }
+
+    /// Creates a mask that fully weights pixels inside any of `rects`, and
+    /// ignores the rest. Rectangles may overlap, and are clipped to the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width of the image in pixels.
+    /// * `height` - Height of the image in pixels.
+    /// * `rects` - The regions of the image to extract colors from.
+    pub fn from_rects(width: usize, height: usize, rects: &[Rect]) -> WeightMask {
+        let mut weights = vec![0; width * height];
+        for rect in rects {
+            let right = rect.x.saturating_add(rect.width).min(width);
+            let bottom = rect.y.saturating_add(rect.height).min(height);
+            for y in rect.y.min(bottom)..bottom {
+                weights[y * width + rect.x.min(right)..y * width + right].fill(255);
+            }
+        }
+        WeightMask::new(width, height, weights)
+    }
+}
+
+/// How an image is reduced in size before its colors are quantized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Downscale {
+    /// Every pixel is used.
+    None,
+
+    /// Every `step`-th pixel of every `step`-th row is used. Fast, but small
+    /// details can be skipped entirely.
+    Stride { step: usize },
+
+    /// Blocks of pixels are averaged, so that at most `max_pixels` remain.
+    /// Every pixel contributes, so small details still shift the averages.
+    AreaAverage { max_pixels: usize },
+}
+
+/// Options used when sampling the pixels of an image with
+/// `ArgbImage::sample`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SamplingOptions {
+    /// How to reduce the image in size.
+    pub downscale: Downscale,
+
+    /// Pixels with an alpha below this are ignored. 255 ignores every pixel
+    /// that isn't fully opaque, 0 uses every pixel. Pixels that are used are
+    /// made opaque.
+    pub alpha_threshold: u8,
+
+    /// How many extra times a pixel at the center of the image counts,
+    /// falling linearly to no extra times at the corners. 0 weights every
+    /// pixel equally, and values above `MAX_CENTER_WEIGHT` are capped.
+    pub center_weight: f64,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        SamplingOptions {
+            downscale: Downscale::AreaAverage {
+                max_pixels: MAX_EXTRACTION_PIXELS,
+            },
+            alpha_threshold: 255,
+            center_weight: 0.0,
+        }
+    }
+}
+
+/// Ranks the colors of an image by how suitable they are as the source color
+/// of a theme, using the default `SamplingOptions`.
+///
+/// # Arguments
+///
+/// * `image` - The image to extract colors from.
+///
+/// # Returns
+///
+/// Colors sorted by suitability for a UI theme, most suitable first. There
+/// will always be at least one color returned.
+pub fn source_colors_from_image(image: &ArgbImage) -> Vec<u32> {
+    source_colors_from_image_with_options(image, &SamplingOptions::default())
+}
+
+/// Ranks the colors of an image by how suitable they are as the source color
+/// of a theme.
+///
+/// The image is sampled with `options`, and the sampled pixels are quantized
+/// and scored.
+///
+/// # Arguments
+///
+/// * `image` - The image to extract colors from.
+/// * `options` - How to sample the pixels of the image.
+///
+/// # Returns
+///
+/// Colors sorted by suitability for a UI theme, most suitable first. There
+/// will always be at least one color returned.
+pub fn source_colors_from_image_with_options(
+    image: &ArgbImage,
+    options: &SamplingOptions,
+) -> Vec<u32> {
+    let pixels = image.sample(options);
+    let quantized: HashMap<u32, u32> = QuantizerCelebi::quantize(&pixels, QUANTIZE_MAX_COLORS);
+    Score::score(&quantized)
+}
+
+/// Ranks the colors of part of an image by how suitable they are as the
+/// source color of a theme. Only pixels with weight in `mask` contribute,
+/// in proportion to their weight.
+///
+/// # Arguments
+///
+/// * `image` - The image to extract colors from.
+/// * `mask` - Weight of each pixel, the same size as the image.
+/// * `options` - How to sample the pixels of the image.
+///
+/// # Returns
+///
+/// Colors sorted by suitability for a UI theme, most suitable first. There
+/// will always be at least one color returned.
+pub fn source_colors_from_image_with_mask(
+    image: &ArgbImage,
+    mask: &WeightMask,
+    options: &SamplingOptions,
+) -> Vec<u32> {
+    Score::score(&image.weighted_population(mask, options))
+}
+
+/// Ranks the colors inside rectangular regions of an image by how suitable
+/// they are as the source color of a theme. See
+/// `source_colors_from_image_with_mask`.
+///
+/// # Arguments
+///
+/// * `image` - The image to extract colors from.
+/// * `rects` - The regions of the image to extract colors from.
+/// * `options` - How to sample the pixels of the image.
+///
+/// # Returns
+///
+/// Colors sorted by suitability for a UI theme, most suitable first. There
+/// will always be at least one color returned.
+pub fn source_colors_from_image_regions(
+    image: &ArgbImage,
+    rects: &[Rect],
+    options: &SamplingOptions,
+) -> Vec<u32> {
+    let mask = WeightMask::from_rects(image.width, image.height, rects);
+    source_colors_from_image_with_mask(image, &mask, options)
+}
+
+/// An error from reading or decoding an image.
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub enum ImageError {
+    /// The image file could not be read.
+    Io(std::io::Error),
+
+    /// The data is neither a PNG nor a JPEG image.
+    UnsupportedFormat,
+
+    /// The PNG image is malformed.
+    Png(png::DecodingError),
+
+    /// The JPEG image is malformed.
+    Jpeg(zune_jpeg::errors::DecodeErrors),
+}
+
+#[cfg(feature = "image")]
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Io(error) => write!(f, "cannot read image: {}", error),
+            ImageError::UnsupportedFormat => write!(f, "image is not a PNG or JPEG"),
+            ImageError::Png(error) => write!(f, "cannot decode PNG: {}", error),
+            ImageError::Jpeg(error) => write!(f, "cannot decode JPEG: {:?}", error),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImageError::Io(error) => Some(error),
+            ImageError::Png(error) => Some(error),
+            ImageError::UnsupportedFormat | ImageError::Jpeg(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<std::io::Error> for ImageError {
+    fn from(error: std::io::Error) -> Self {
+        ImageError::Io(error)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<png::DecodingError> for ImageError {
+    fn from(error: png::DecodingError) -> Self {
+        ImageError::Png(error)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<zune_jpeg::errors::DecodeErrors> for ImageError {
+    fn from(error: zune_jpeg::errors::DecodeErrors) -> Self {
+        ImageError::Jpeg(error)
+    }
+}
+
+#[cfg(feature = "image")]
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+#[cfg(feature = "image")]
+const JPEG_SIGNATURE: [u8; 3] = [0xff, 0xd8, 0xff];
+
+#[cfg(feature = "image")]
+fn decode_png(bytes: &[u8]) -> Result<ArgbImage, ImageError> {
+    let mut decoder = png::Decoder::new(bytes);
+    // Expand palettes and low bit depths, and strip 16 bit samples to 8 bits.
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    let channels = info.color_type.samples();
+    let width = info.width as usize;
+    let height = info.height as usize;
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in buffer.chunks(info.line_size).take(height) {
+        for sample in row[..width * channels].chunks_exact(channels) {
+            pixels.push(argb_from_samples(sample));
+        }
+    }
+    Ok(ArgbImage::new(width, height, pixels))
+}
+
+#[cfg(feature = "image")]
+fn decode_jpeg(bytes: &[u8]) -> Result<ArgbImage, ImageError> {
+    use zune_jpeg::zune_core::{colorspace::ColorSpace, options::DecoderOptions};
+
+    let options = DecoderOptions::default().jpeg_set_out_colorspace(ColorSpace::RGB);
+    let mut decoder = zune_jpeg::JpegDecoder::new_with_options(bytes, options);
+    let buffer = decoder.decode()?;
+    let info = decoder
+        .info()
+        .expect("headers are decoded once decoding succeeds");
+    let width = info.width as usize;
+    let height = info.height as usize;
+
+    let pixels = buffer.chunks_exact(3).map(argb_from_samples).collect();
+    Ok(ArgbImage::new(width, height, pixels))
+}
+
+/// Converts the 8 bit samples of one gray, gray and alpha, RGB or RGBA pixel
+/// to ARGB.
+#[cfg(feature = "image")]
+fn argb_from_samples(sample: &[u8]) -> u32 {
+    let rgba = match *sample {
+        [gray] => Rgba {
+            r: gray,
+            g: gray,
+            b: gray,
+            a: 255,
+        },
+        [gray, a] => Rgba {
+            r: gray,
+            g: gray,
+            b: gray,
+            a,
+        },
+        [r, g, b] => Rgba { r, g, b, a: 255 },
+        [r, g, b, a] => Rgba { r, g, b, a },
+        _ => unreachable!("unexpected sample count {}", sample.len()),
+    };
+    argb_from_rgba(&rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hct::Hct, utils::math_utils::difference_degrees};
+
+    /// A 200x150 image of three bands of color, darkened by noise from a fixed
+    /// seed. Every channel of every band is at least 0x0f, so noise never
+    /// borrows from the next channel.
+    fn banded_image() -> ArgbImage {
+        let bands = [0xff3f51b5u32, 0xffe91e63, 0xff8bc34a];
+        let mut state = 0x2545f491u32;
+        let pixels = (0..200 * 150)
+            .map(|index| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                let noise = (state >> 24) & 0x0f;
+                bands[index / 200 / 50] - noise * 0x010101
+            })
+            .collect();
+        ArgbImage::new(200, 150, pixels)
+    }
+
+    #[test]
+    fn sample_without_downscale_drops_translucent_pixels() {
+        let image = ArgbImage::new(2, 2, vec![0xffff0000, 0x8000ff00, 0xff0000ff, 0x00ffffff]);
+        let options = SamplingOptions {
+            downscale: Downscale::None,
+            ..SamplingOptions::default()
+        };
+        assert_eq!(image.sample(&options), vec![0xffff0000, 0xff0000ff]);
+
+        let options = SamplingOptions {
+            alpha_threshold: 0x80,
+            ..options
+        };
+        assert_eq!(
+            image.sample(&options),
+            vec![0xffff0000, 0xff00ff00, 0xff0000ff]
+        );
+    }
+
+    #[test]
+    fn sample_with_stride_skips_pixels() {
+        let image = ArgbImage::new(3, 3, (0..9).map(|index| 0xff000000 | index).collect());
+        let options = SamplingOptions {
+            downscale: Downscale::Stride { step: 2 },
+            ..SamplingOptions::default()
+        };
+        assert_eq!(
+            image.sample(&options),
+            vec![0xff000000, 0xff000002, 0xff000006, 0xff000008]
+        );
+    }
+
+    #[test]
+    fn sample_with_area_average_averages_linear_rgb() {
+        let image = ArgbImage::new(2, 2, vec![0xff000000, 0xffffffff, 0xffffffff, 0xff000000]);
+        let options = SamplingOptions {
+            downscale: Downscale::AreaAverage { max_pixels: 1 },
+            ..SamplingOptions::default()
+        };
+        // Half of white's linear intensity, not half of its sRGB value.
+        assert_eq!(image.sample(&options), vec![0xffbcbcbc]);
+    }
+
+    #[test]
+    fn sample_with_center_weight_repeats_center_pixels() {
+        let image = ArgbImage::new(3, 1, vec![0xffff0000, 0xff00ff00, 0xff0000ff]);
+        let options = SamplingOptions {
+            downscale: Downscale::None,
+            center_weight: 2.0,
+            ..SamplingOptions::default()
+        };
+        let pixels = image.sample(&options);
+        let count = |argb| pixels.iter().filter(|&&pixel| pixel == argb).count();
+        assert_eq!(count(0xff00ff00), 3);
+        assert_eq!(count(0xffff0000), count(0xff0000ff));
+        assert!(count(0xffff0000) < 3);
+    }
+
+    #[test]
+    fn center_weight_is_capped() {
+        let image = ArgbImage::new(1, 1, vec![0xffff0000]);
+        let options = SamplingOptions {
+            downscale: Downscale::None,
+            center_weight: f64::MAX,
+            ..SamplingOptions::default()
+        };
+        assert_eq!(image.sample(&options).len(), 1 + MAX_CENTER_WEIGHT as usize);
+    }
+
+    #[test]
+    fn populations_are_scaled_to_fit_u32() {
+        let population = HashMap::from([(0xffff0000, 3 * u32::MAX as u64), (0xff0000ff, 3)]);
+        assert_eq!(
+            populations_to_u32(population),
+            HashMap::from([(0xffff0000, u32::MAX), (0xff0000ff, 1)])
+        );
+
+        let population = HashMap::from([(0xffff0000, 5), (0xff0000ff, 3)]);
+        assert_eq!(
+            populations_to_u32(population),
+            HashMap::from([(0xffff0000, 5), (0xff0000ff, 3)])
+        );
+    }
+
+    #[test]
+    fn source_colors_are_deterministic() {
+        let image = banded_image();
+        let colors = source_colors_from_image(&image);
+        assert_eq!(colors, source_colors_from_image(&image.clone()));
+        // A snapshot: changes to sampling, quantizing or scoring that move it
+        // should be deliberate.
+        assert_eq!(colors, vec![0xff82ba41, 0xffe51a5f, 0xff384aae]);
+    }
+
+    #[test]
+    fn region_over_one_band_ranks_its_color_first() {
+        let image = banded_image();
+        for (band, argb) in [0xff3f51b5u32, 0xffe91e63, 0xff8bc34a]
+            .into_iter()
+            .enumerate()
+        {
+            let rect = Rect {
+                x: 0,
+                y: band * 50,
+                width: 200,
+                height: 50,
+            };
+            let colors =
+                source_colors_from_image_regions(&image, &[rect], &SamplingOptions::default());
+            let hue = Hct::from_int(colors[0]).hue();
+            assert!(
+                difference_degrees(hue, Hct::from_int(argb).hue()) < 5.0,
+                "band {band} ranked {:08x} first",
+                colors[0]
+            );
+        }
+    }
+
+    #[test]
+    fn mask_without_weight_falls_back_to_default_color() {
+        let image = banded_image();
+        let mask = WeightMask::new(200, 150, vec![0; 200 * 150]);
+        assert!(image
+            .weighted_population(&mask, &SamplingOptions::default())
+            .is_empty());
+        assert_eq!(
+            source_colors_from_image_with_mask(&image, &mask, &SamplingOptions::default()),
+            vec![0xff4285f4]
+        );
+        assert_eq!(
+            source_colors_from_image_regions(&image, &[], &SamplingOptions::default()),
+            vec![0xff4285f4]
+        );
+    }
+
+    #[test]
+    fn rects_are_clipped_to_the_image() {
+        let rects = [
+            // Overlapping, and past the right and bottom edges.
+            Rect {
+                x: 2,
+                y: 1,
+                width: 10,
+                height: 10,
+            },
+            Rect {
+                x: 1,
+                y: 1,
+                width: 2,
+                height: 1,
+            },
+            // Entirely outside, and large enough to overflow.
+            Rect {
+                x: 100,
+                y: 0,
+                width: 5,
+                height: 5,
+            },
+            Rect {
+                x: usize::MAX,
+                y: usize::MAX,
+                width: usize::MAX,
+                height: usize::MAX,
+            },
+        ];
+        let mask = WeightMask::from_rects(4, 3, &rects);
+        #[rustfmt::skip]
+        assert_eq!(mask.weights, vec![
+            0, 0, 0, 0,
+            0, 255, 255, 255,
+            0, 0, 255, 255,
+        ]);
+
+        let image = banded_image();
+        let colors = source_colors_from_image_regions(&image, &rects, &SamplingOptions::default());
+        assert!(!colors.is_empty());
+    }
+
+    #[test]
+    fn mask_weights_scale_population() {
+        let image = ArgbImage::new(3, 1, vec![0xffff0000, 0xff0000ff, 0xff0000ff]);
+        let mask = WeightMask::new(3, 1, vec![255, 51, 0]);
+        let options = SamplingOptions {
+            downscale: Downscale::None,
+            ..SamplingOptions::default()
+        };
+        assert_eq!(
+            image.weighted_population(&mask, &options),
+            HashMap::from([(0xffff0000, 255), (0xff0000ff, 51)])
+        );
+
+        // Weighting the pink band above the others ranks it first, though the
+        // whole image ranks green first.
+        let image = banded_image();
+        let weights = (0..200 * 150)
+            .map(|index| if index / 200 / 50 == 1 { 255 } else { 16 })
+            .collect();
+        let mask = WeightMask::new(200, 150, weights);
+        let colors = source_colors_from_image_with_mask(&image, &mask, &SamplingOptions::default());
+        assert_eq!(colors[0], source_colors_from_image(&image)[1]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_png_expands_palette_with_transparency() {
+        let image = ArgbImage::decode(include_bytes!("testdata/palette.png")).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.pixels, vec![0xffff0000, 0x800000ff]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_png_expands_gray_and_alpha() {
+        let image = ArgbImage::decode(include_bytes!("testdata/gray-alpha.png")).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.pixels, vec![0xff404040, 0x80c0c0c0]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_png_strips_16_bit_samples() {
+        // (0x1234, 0x5678, 0x9abc) above (0xffff, 0, 0).
+        let image = ArgbImage::decode(include_bytes!("testdata/rgb-16-bit.png")).unwrap();
+        assert_eq!((image.width, image.height), (1, 2));
+        assert_eq!(image.pixels, vec![0xff12569a, 0xffff0000]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_jpeg_converts_to_rgb() {
+        // Two 8x8 blocks encoded from 0xffe91e63 and 0xff3f51b5, which
+        // round to the nearest YCbCr values.
+        let image = ArgbImage::decode(include_bytes!("testdata/two-colors.jpg")).unwrap();
+        assert_eq!((image.width, image.height), (16, 8));
+        for (index, &pixel) in image.pixels.iter().enumerate() {
+            let expected = if index % 16 < 8 {
+                0xffea1e63
+            } else {
+                0xff3f51b5
+            };
+            assert_eq!(pixel, expected, "pixel {index}");
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_rejects_unsupported_and_malformed_images() {
+        assert!(matches!(
+            ArgbImage::decode(b"GIF89a"),
+            Err(ImageError::UnsupportedFormat)
+        ));
+        assert!(matches!(
+            ArgbImage::decode(&[]),
+            Err(ImageError::UnsupportedFormat)
+        ));
+
+        let png = include_bytes!("testdata/palette.png");
+        assert!(matches!(
+            ArgbImage::decode(&png[..40]),
+            Err(ImageError::Png(_))
+        ));
+
+        let jpeg = include_bytes!("testdata/two-colors.jpg");
+        assert!(matches!(
+            ArgbImage::decode(&jpeg[..100]),
+            Err(ImageError::Jpeg(_))
+        ));
+
+        assert!(matches!(
+            ArgbImage::open("testdata/missing.png"),
+            Err(ImageError::Io(_))
+        ));
+    }
+}
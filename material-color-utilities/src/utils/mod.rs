@@ -1,3 +1,8 @@
 pub mod color_utils;
+pub mod css_utils;
+pub mod image_utils;
 pub mod math_utils;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_utils;
 pub mod string_utils;
+pub mod theme_utils;
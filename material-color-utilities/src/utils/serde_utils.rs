@@ -0,0 +1,26 @@
+// Helpers for implementing the serde traits, available with the `serde`
+// feature.
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// Serializes a value as the string its `Display` implementation writes.
+pub(crate) fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// Deserializes a value from a string with its `FromStr` implementation.
+pub(crate) fn deserialize_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
@@ -1,47 +1,405 @@
 // Utility methods for hexadecimal representations of colors.
+use std::{error::Error, fmt, str::FromStr};
+
 use super::color_utils::*;
 
+/// The order of the channels of a hex color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HexFormat {
+    /// `#rrggbb`. Alpha is dropped when formatting, and parsed colors are
+    /// opaque. Parses 3 or 6 hex digits.
+    #[default]
+    Rgb,
+
+    /// `#rrggbbaa`, as in CSS. Parses 3, 4, 6 or 8 hex digits, alpha last.
+    Css,
+
+    /// `#aarrggbb`, as in Android resources. Parses 3, 4, 6 or 8 hex digits,
+    /// alpha first.
+    Android,
+}
+
 /// Returns the hex string representing a color.
 ///
 /// - `argb`: ARGB representation of a color.
-/// - Returns: Hex string representing color, ex. #ff0000 for red.
+/// - Returns: Hex string representing color, ex. #ff0000 for red. Alpha is dropped, use
+///   `hex_from_argb_with_format` to keep it.
 pub fn hex_from_argb(argb: u32) -> String {
+    hex_from_argb_with_format(argb, HexFormat::Rgb)
+}
+
+/// Returns the hex string representing a color in a given format.
+///
+/// - `argb`: ARGB representation of a color.
+/// - `format`: The order of the channels, and whether alpha is included.
+/// - Returns: Hex string representing color, ex. #ff000080 in `HexFormat::Css` and #80ff0000
+///   in `HexFormat::Android` for half transparent red.
+pub fn hex_from_argb_with_format(argb: u32, format: HexFormat) -> String {
+    let a = alpha_from_argb(argb);
     let r = red_from_argb(argb);
     let g = green_from_argb(argb);
     let b = blue_from_argb(argb);
 
-    format!("#{:02x}{:02x}{:02x}", r, g, b)
+    match format {
+        HexFormat::Rgb => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        HexFormat::Css => format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a),
+        HexFormat::Android => format!("#{:02x}{:02x}{:02x}{:02x}", a, r, g, b),
+    }
 }
 
 /// Returns the ARGB representation of a color from a hex string.
 ///
 /// - `hex`: String representing color as hex code. Accepts strings with or without leading #,
-///          and string representing the color using 3, 6, or 8 hex characters.
+///   and string representing the color using 3, 4, 6, or 8 hex characters. 4 and 8 characters
+///   include alpha first, as in `HexFormat::Android`.
 /// - Returns: ARGB representation of color.
+/// - Panics: If `hex` is not a valid hex color. Use `try_argb_from_hex` for input that may
+///   be invalid.
 pub fn argb_from_hex(hex: &str) -> u32 {
-    let hex = hex.trim_start_matches('#');
-    let (r, g, b) = match hex.len() {
-        3 => (
-            parse_int_hex(&hex[0..1].repeat(2)),
-            parse_int_hex(&hex[1..2].repeat(2)),
-            parse_int_hex(&hex[2..3].repeat(2)),
-        ),
-        6 => (
-            parse_int_hex(&hex[0..2]),
-            parse_int_hex(&hex[2..4]),
-            parse_int_hex(&hex[4..6]),
-        ),
-        8 => (
-            parse_int_hex(&hex[2..4]),
-            parse_int_hex(&hex[4..6]),
-            parse_int_hex(&hex[6..8]),
-        ),
-        _ => panic!("unexpected hex {}", hex),
+    try_argb_from_hex(hex).unwrap_or_else(|error| panic!("unexpected hex {}: {}", hex, error))
+}
+
+/// Returns the ARGB representation of a color from a hex string, or why it is not a valid
+/// hex color.
+///
+/// - `hex`: String representing color as hex code. Accepts strings with or without leading #,
+///   and string representing the color using 3, 4, 6, or 8 hex characters. 4 and 8 characters
+///   include alpha first, as in `HexFormat::Android`.
+/// - Returns: ARGB representation of color, or the position and reason of the first problem
+///   found in `hex`.
+pub fn try_argb_from_hex(hex: &str) -> Result<u32, ColorParseError> {
+    try_argb_from_hex_with_format(hex, HexFormat::Android)
+}
+
+/// Returns the ARGB representation of a color from a hex string in a given format, or why it
+/// is not a valid hex color.
+///
+/// - `hex`: String representing color as hex code, with or without leading #.
+/// - `format`: The order of the channels, and whether alpha is included.
+/// - Returns: ARGB representation of color, or the position and reason of the first problem
+///   found in `hex`.
+pub fn try_argb_from_hex_with_format(hex: &str, format: HexFormat) -> Result<u32, ColorParseError> {
+    let digits = hex.trim_start_matches('#');
+    let start = hex.len() - digits.len();
+    if let Some((index, digit)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(ColorParseError {
+            position: start + index,
+            kind: ColorParseErrorKind::InvalidDigit(digit),
+        });
+    }
+
+    // Expand shorthand, ex. f80 to ff8800, so every channel has 2 digits.
+    let digits = match (digits.len(), format) {
+        (3, _) | (4, HexFormat::Css | HexFormat::Android) => {
+            digits.chars().flat_map(|digit| [digit, digit]).collect()
+        }
+        (6, _) | (8, HexFormat::Css | HexFormat::Android) => digits.to_string(),
+        (length, _) => {
+            return Err(ColorParseError {
+                position: start,
+                kind: ColorParseErrorKind::InvalidLength(length),
+            })
+        }
+    };
+    let channel = |index: usize| parse_int_hex(&digits[index * 2..index * 2 + 2]);
+    let (a, r, g, b) = match (digits.len(), format) {
+        (6, _) => (255, channel(0), channel(1), channel(2)),
+        (_, HexFormat::Android) => (channel(0), channel(1), channel(2), channel(3)),
+        _ => (channel(3), channel(0), channel(1), channel(2)),
     };
 
-    ((255 << 24) | ((r & 0x0ff) << 16) | ((g & 0x0ff) << 8) | (b & 0x0ff)) as u32
+    Ok((a << 24) | (r << 16) | (g << 8) | b)
 }
 
+/// Only called with ASCII hex digits, which always parse.
 fn parse_int_hex(value: &str) -> u32 {
     u32::from_str_radix(value, 16).unwrap()
 }
+
+/// The reason a string is not a valid color.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColorParseErrorKind {
+    /// A character that is not a hex digit.
+    InvalidDigit(char),
+
+    /// A count of hex digits that is not supported by the format, ex. other
+    /// than 3 or 6 for `HexFormat::Rgb`.
+    InvalidLength(usize),
+
+    /// A named color, function or color space that is not known.
+    UnknownColor(String),
+
+    /// Text that is not valid where it appears.
+    Unexpected(String),
+
+    /// The input ended before the color was complete.
+    UnexpectedEnd,
+}
+
+/// An error from parsing a color string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorParseError {
+    /// Byte offset into the input where the problem starts.
+    pub position: usize,
+
+    /// What is wrong with the input.
+    pub kind: ColorParseErrorKind,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ColorParseErrorKind::InvalidDigit(digit) => write!(
+                f,
+                "invalid hex digit '{}' at position {}",
+                digit, self.position
+            ),
+            ColorParseErrorKind::InvalidLength(length) => write!(
+                f,
+                "unsupported count of {} hex digits at position {}",
+                length, self.position
+            ),
+            ColorParseErrorKind::UnknownColor(name) => {
+                write!(f, "unknown color '{}' at position {}", name, self.position)
+            }
+            ColorParseErrorKind::Unexpected(text) => {
+                write!(f, "unexpected '{}' at position {}", text, self.position)
+            }
+            ColorParseErrorKind::UnexpectedEnd => {
+                write!(f, "unexpected end of color at position {}", self.position)
+            }
+        }
+    }
+}
+
+impl Error for ColorParseError {}
+
+impl FromStr for Rgba {
+    type Err = ColorParseError;
+
+    /// Parses a hex color in `HexFormat::Css`, ex. #ff000080.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        try_argb_from_hex_with_format(hex, HexFormat::Css).map(rgba_from_argb)
+    }
+}
+
+impl fmt::Display for Rgba {
+    /// Formats the color in `HexFormat::Css`, ex. #ff000080.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex_from_argb_with_format(
+            argb_from_rgba(self),
+            HexFormat::Css,
+        ))
+    }
+}
+
+impl FromStr for Argb {
+    type Err = ColorParseError;
+
+    /// Parses a hex color in `HexFormat::Android`, ex. #80ff0000.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        try_argb_from_hex(hex).map(Argb)
+    }
+}
+
+impl fmt::Display for Argb {
+    /// Formats the color in `HexFormat::Android`, ex. #80ff0000.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex_from_argb_with_format(self.0, HexFormat::Android))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Rgba {
+    /// Serializes the color as a string in `HexFormat::Css`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serde_utils::serialize_display(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Rgba {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::serde_utils::deserialize_from_str(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Argb {
+    /// Serializes the color as a string in `HexFormat::Android`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serde_utils::serialize_display(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Argb {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::serde_utils::deserialize_from_str(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(position: usize, kind: ColorParseErrorKind) -> Result<u32, ColorParseError> {
+        Err(ColorParseError { position, kind })
+    }
+
+    #[test]
+    fn translucent_colors_round_trip_in_both_alpha_orders() {
+        // A 32% black scrim and a half transparent red.
+        for argb in [0x52000000, 0x80ff0000] {
+            let css = hex_from_argb_with_format(argb, HexFormat::Css);
+            let android = hex_from_argb_with_format(argb, HexFormat::Android);
+            assert_eq!(
+                try_argb_from_hex_with_format(&css, HexFormat::Css),
+                Ok(argb)
+            );
+            assert_eq!(try_argb_from_hex(&android), Ok(argb));
+
+            let rgba = rgba_from_argb(argb);
+            assert_eq!(rgba.to_string(), css);
+            assert_eq!(css.parse(), Ok(rgba));
+            assert_eq!(Argb(argb).to_string(), android);
+            assert_eq!(android.parse(), Ok(Argb(argb)));
+        }
+        assert_eq!(
+            hex_from_argb_with_format(0x52000000, HexFormat::Css),
+            "#00000052"
+        );
+        assert_eq!(
+            hex_from_argb_with_format(0x52000000, HexFormat::Android),
+            "#52000000"
+        );
+        assert_eq!(hex_from_argb(0x52000000), "#000000");
+    }
+
+    #[test]
+    fn shorthand_expands_each_digit() {
+        assert_eq!(
+            try_argb_from_hex_with_format("#f80", HexFormat::Rgb),
+            Ok(0xffff8800)
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#f80", HexFormat::Css),
+            Ok(0xffff8800)
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#f808", HexFormat::Css),
+            Ok(0x88ff8800)
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#8f80", HexFormat::Android),
+            Ok(0x88ff8800)
+        );
+        assert_eq!(
+            "#f808".parse(),
+            Ok(Rgba {
+                r: 0xff,
+                g: 0x88,
+                b: 0x00,
+                a: 0x88
+            })
+        );
+        assert_eq!("#8f80".parse(), Ok(Argb(0x88ff8800)));
+    }
+
+    #[test]
+    fn rgb_format_rejects_alpha() {
+        assert_eq!(
+            try_argb_from_hex_with_format("#ff880080", HexFormat::Rgb),
+            error(1, ColorParseErrorKind::InvalidLength(8))
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#f808", HexFormat::Rgb),
+            error(1, ColorParseErrorKind::InvalidLength(4))
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#ff8800", HexFormat::Rgb),
+            Ok(0xffff8800)
+        );
+    }
+
+    #[test]
+    fn invalid_digits_are_reported_after_the_hash() {
+        assert_eq!(
+            try_argb_from_hex("#12z456"),
+            error(3, ColorParseErrorKind::InvalidDigit('z'))
+        );
+        assert_eq!(
+            try_argb_from_hex("12z456"),
+            error(2, ColorParseErrorKind::InvalidDigit('z'))
+        );
+        assert_eq!(
+            try_argb_from_hex_with_format("#ff00ff0g", HexFormat::Css),
+            error(8, ColorParseErrorKind::InvalidDigit('g'))
+        );
+        assert_eq!(
+            try_argb_from_hex(" #fff"),
+            error(0, ColorParseErrorKind::InvalidDigit(' '))
+        );
+    }
+
+    #[test]
+    fn invalid_lengths_depend_on_the_format() {
+        for format in [HexFormat::Rgb, HexFormat::Css, HexFormat::Android] {
+            for digits in ["", "f", "ff", "fffff", "fffffff", "fffffffff"] {
+                assert_eq!(
+                    try_argb_from_hex_with_format(&format!("#{digits}"), format),
+                    error(1, ColorParseErrorKind::InvalidLength(digits.len())),
+                    "{format:?} #{digits}"
+                );
+            }
+        }
+        for digits in ["ffff", "ffffffff"] {
+            assert_eq!(
+                try_argb_from_hex_with_format(digits, HexFormat::Rgb),
+                error(0, ColorParseErrorKind::InvalidLength(digits.len()))
+            );
+        }
+    }
+
+    #[test]
+    fn non_ascii_input_is_an_invalid_digit() {
+        // Positions are byte offsets, so they stay valid for slicing `hex`.
+        let hex = "#ffé000";
+        let error = try_argb_from_hex(hex).unwrap_err();
+        assert_eq!(error.kind, ColorParseErrorKind::InvalidDigit('é'));
+        assert_eq!(&hex[error.position..], "é000");
+
+        let error = try_argb_from_hex("#ff00０0").unwrap_err();
+        assert_eq!(error.kind, ColorParseErrorKind::InvalidDigit('０'));
+        assert_eq!(error.position, 5);
+    }
+
+    #[test]
+    fn errors_describe_the_problem() {
+        assert_eq!(
+            try_argb_from_hex("#12z456").unwrap_err().to_string(),
+            "invalid hex digit 'z' at position 3"
+        );
+        assert_eq!(
+            try_argb_from_hex("#12345").unwrap_err().to_string(),
+            "unsupported count of 5 hex digits at position 1"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_round_trips_argb_and_rgba() {
+        let argb = Argb(0x80ff0000);
+        let json = serde_json::to_string(&argb).unwrap();
+        assert_eq!(json, r##""#80ff0000""##);
+        assert_eq!(serde_json::from_str::<Argb>(&json).unwrap(), argb);
+
+        let rgba = rgba_from_argb(argb.0);
+        let json = serde_json::to_string(&rgba).unwrap();
+        assert_eq!(json, r##""#ff000080""##);
+        assert_eq!(serde_json::from_str::<Rgba>(&json).unwrap(), rgba);
+
+        assert!(serde_json::from_str::<Argb>(r##""#ff00zz""##).is_err());
+        assert!(serde_json::from_str::<Rgba>("4278190080").is_err());
+    }
+}
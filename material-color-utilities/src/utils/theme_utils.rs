@@ -0,0 +1,117 @@
+// Utility methods for building complete themes.
+#[cfg(feature = "image")]
+use std::path::Path;
+
+use crate::{palettes::CorePalette, scheme::Scheme};
+
+#[cfg(feature = "image")]
+use super::image_utils::ImageError;
+use super::image_utils::{source_colors_from_image, ArgbImage};
+
+/// A complete Material theme: the palettes generated from a source color and
+/// the light and dark schemes built from them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// The color the theme is generated from.
+    pub source: u32,
+
+    /// Other colors that would also make a good source color, most suitable
+    /// first. Empty when the theme was not extracted from an image.
+    pub alternates: Vec<u32>,
+
+    pub palettes: CorePalette,
+    pub light: Scheme,
+    pub dark: Scheme,
+}
+
+/// Generates a theme from a source color.
+///
+/// # Arguments
+///
+/// * `source` - ARGB representation of the source color.
+///
+/// # Returns
+///
+/// A theme with light and dark schemes, and no alternates.
+pub fn theme_from_source_color(source: u32) -> Theme {
+    theme_from_ranked_colors(vec![source])
+}
+
+/// Generates a theme from the most suitable color of an image.
+///
+/// # Arguments
+///
+/// * `image` - The image to extract colors from.
+///
+/// # Returns
+///
+/// A theme whose source is the most suitable color of the image, and whose
+/// alternates are the remaining suitable colors.
+pub fn theme_from_argb_image(image: &ArgbImage) -> Theme {
+    theme_from_ranked_colors(source_colors_from_image(image))
+}
+
+/// Decodes a PNG or JPEG file and generates a theme from its most suitable
+/// color. See `theme_from_argb_image`.
+///
+/// # Arguments
+///
+/// * `path` - Path of the image file.
+#[cfg(feature = "image")]
+pub fn theme_from_image(path: impl AsRef<Path>) -> Result<Theme, ImageError> {
+    Ok(theme_from_argb_image(&ArgbImage::open(path)?))
+}
+
+/// Decodes a PNG or JPEG image held in memory and generates a theme from its
+/// most suitable color. See `theme_from_argb_image`.
+///
+/// # Arguments
+///
+/// * `bytes` - Contents of a PNG or JPEG file.
+#[cfg(feature = "image")]
+pub fn theme_from_image_bytes(bytes: &[u8]) -> Result<Theme, ImageError> {
+    Ok(theme_from_argb_image(&ArgbImage::decode(bytes)?))
+}
+
+/// Generates a theme from the first of `ranked` colors, keeping the rest as
+/// alternates.
+fn theme_from_ranked_colors(mut ranked: Vec<u32>) -> Theme {
+    let source = ranked.remove(0);
+    let palettes = CorePalette::of(source);
+    Theme {
+        source,
+        alternates: ranked,
+        light: Scheme::light_from_core_palette(&palettes),
+        dark: Scheme::dark_from_core_palette(&palettes),
+        palettes,
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_from_image_bytes_uses_most_suitable_color() {
+        let theme = theme_from_image_bytes(include_bytes!("testdata/two-colors.jpg")).unwrap();
+
+        assert_eq!(theme.source, 0xffea1e63);
+        assert_eq!(theme.alternates, vec![0xff3f51b5]);
+        let from_source = theme_from_source_color(0xffea1e63);
+        assert_eq!(theme.palettes, from_source.palettes);
+        assert_eq!(theme.light, from_source.light);
+        assert_eq!(theme.dark, from_source.dark);
+    }
+
+    #[test]
+    fn theme_from_image_bytes_reports_decoding_errors() {
+        assert!(matches!(
+            theme_from_image_bytes(b"GIF89a"),
+            Err(ImageError::UnsupportedFormat)
+        ));
+        assert!(matches!(
+            theme_from_image("testdata/missing.png"),
+            Err(ImageError::Io(_))
+        ));
+    }
+}
@@ -1,14 +1,27 @@
 use std::{
-    fs::{metadata, File},
+    fs::{self, metadata, File},
     io::Write,
     path::PathBuf,
 };
 
-use clap::{command, Arg, Command};
+use clap::{command, Arg, ArgAction, ArgMatches, Command};
 use material_color_utilities::{
+    contrast::ContrastAlgorithm,
+    export::{
+        android_colors_xml, asset_catalog_files, compose_color_kt, compose_theme_kt, design_tokens,
+        flutter_color_schemes_dart, swift_color_extension, MaterialTheme,
+    },
     palettes::*,
-    scheme::Scheme,
-    utils::string_utils::{argb_from_hex, hex_from_argb},
+    scheme::{DynamicScheme, Scheme},
+    utils::{
+        color_utils::Argb,
+        css_utils::argb_from_css,
+        string_utils::{
+            hex_from_argb, try_argb_from_hex_with_format, ColorParseError, ColorParseErrorKind,
+            HexFormat,
+        },
+        theme_utils::theme_from_image,
+    },
 };
 
 fn main() {
@@ -20,86 +33,391 @@ fn main() {
         .subcommand(
             Command::new("generate-css")
                 .about("Generate CSS with color settings")
+                .args(core_color_args())
                 .arg(
-                    Arg::new("primary")
-                        .short('p')
-                        .long("primary")
-                        .required(true)
-                        .value_name("#001122")
-                        .help("Sets the primary color"),
+                    Arg::new("contrast")
+                        .short('c')
+                        .long("contrast")
+                        .value_name("LEVEL")
+                        .value_parser(parse_contrast_level)
+                        .allow_negative_numbers(true)
+                        .help("Sets the contrast level, from -1.0 (minimum) to 1.0 (maximum)"),
                 )
                 .arg(
-                    Arg::new("secondary")
-                        .short('s')
-                        .long("secondary")
-                        .value_name("#001122")
-                        .help("Sets the secondary color"),
+                    Arg::new("apca")
+                        .long("apca")
+                        .action(ArgAction::SetTrue)
+                        .help("Measures contrast with APCA instead of WCAG 2 ratios"),
                 )
+                .arg(format_arg())
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("generate-json")
+                .about("Generate a Material Theme Builder JSON theme")
+                .args(core_color_args())
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("generate-android")
+                .about("Generate Android color resources and a Jetpack Compose theme")
+                .args(core_color_args())
                 .arg(
-                    Arg::new("tertiary")
-                        .short('t')
-                        .long("tertiary")
-                        .value_name("#001122")
-                        .help("Sets the tertiary color"),
+                    Arg::new("package")
+                        .long("package")
+                        .value_name("PACKAGE")
+                        .default_value("com.example.ui.theme")
+                        .value_parser(parse_kotlin_package)
+                        .help("Sets the Kotlin package of Color.kt and Theme.kt"),
                 )
                 .arg(
-                    Arg::new("error")
-                        .short('e')
-                        .long("error")
-                        .value_name("#001122")
-                        .help("Sets the error color"),
+                    Arg::new("theme-name")
+                        .long("theme-name")
+                        .value_name("NAME")
+                        .default_value("AppTheme")
+                        .value_parser(parse_kotlin_identifier)
+                        .help("Sets the name of the theme composable"),
                 )
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("generate-ios")
+                .about("Generate an Xcode asset catalog of color sets")
+                .args(core_color_args())
                 .arg(
-                    Arg::new("output")
-                        .short('o')
-                        .long("output")
+                    Arg::new("swift")
+                        .long("swift")
+                        .action(ArgAction::SetTrue)
+                        .help("Also generates a Swift extension on Color exposing each color set"),
+                )
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("generate-dart")
+                .about("Generate Flutter ColorScheme constants")
+                .args(core_color_args())
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("from-image")
+                .about("Generate CSS with color settings extracted from an image")
+                .arg(
+                    Arg::new("file")
                         .required(true)
-                        .value_name("DIR")
-                        .help("Sets the output directory"),
-                ),
+                        .value_name("FILE")
+                        .help("Sets the PNG or JPEG image to extract colors from"),
+                )
+                .arg(format_arg())
+                .arg(output_arg()),
         )
         .get_matches();
 
     // Check if the 'generate-css' command was used
     if let Some(matches) = matches.subcommand_matches("generate-css") {
         // Create the core palette based off the colors that were passed in
-        let colors = CorePaletteColors {
-            primary: argb_from_hex(matches.get_one::<String>("primary").unwrap()),
-            secondary: matches
-                .get_one::<String>("secondary")
-                .map(|hex| argb_from_hex(hex)),
-            tertiary: matches
-                .get_one::<String>("tertiary")
-                .map(|hex| argb_from_hex(hex)),
-            neutral: None,
-            neutral_variant: None,
-            error: matches
-                .get_one::<String>("error")
-                .map(|hex| argb_from_hex(hex)),
-        };
-        let core = CorePalette::from_colors(colors);
+        let core = CorePalette::from_colors(core_palette_colors(matches));
 
         // Create a light and dark scheme from the core palette
+        let contrast_level = matches.get_one::<f64>("contrast").copied();
+        let apca = matches.get_flag("apca");
+        let (light, dark) = if contrast_level.is_some() || apca {
+            let contrast_algorithm = if apca {
+                ContrastAlgorithm::Apca
+            } else {
+                ContrastAlgorithm::Wcag
+            };
+            let scheme_for = |is_dark| {
+                let mut scheme =
+                    DynamicScheme::from_core_palette(&core, is_dark, contrast_level.unwrap_or(0.0));
+                scheme.contrast_algorithm = contrast_algorithm;
+                Scheme::from_dynamic_scheme(&scheme)
+            };
+            (scheme_for(false), scheme_for(true))
+        } else {
+            (
+                Scheme::light_from_core_palette(&core),
+                Scheme::dark_from_core_palette(&core),
+            )
+        };
+
+        write_tokens(matches, &core, &light, &dark);
+    }
+
+    // Check if the 'generate-json' command was used
+    if let Some(matches) = matches.subcommand_matches("generate-json") {
+        let theme = MaterialTheme::from_colors(core_palette_colors(matches));
+        write_file(
+            matches.get_one::<String>("output").unwrap(),
+            "material-theme.json",
+            &format!("{}\n", theme.to_json()),
+        );
+    }
+
+    // Check if the 'generate-android' command was used
+    if let Some(matches) = matches.subcommand_matches("generate-android") {
+        let core = CorePalette::from_colors(core_palette_colors(matches));
         let light = Scheme::light_from_core_palette(&core);
         let dark = Scheme::dark_from_core_palette(&core);
+        let package = matches.get_one::<String>("package").unwrap();
+        let theme_name = matches.get_one::<String>("theme-name").unwrap();
 
-        // Verify we can write to the output directory
         let out_dir = matches.get_one::<String>("output").unwrap();
-        if !is_directory_writable(&out_dir) {
-            println!("Cannot write to '{}', quitting", out_dir);
-            return;
+        write_file(out_dir, "values/colors.xml", &android_colors_xml(&light));
+        write_file(
+            out_dir,
+            "values-night/colors.xml",
+            &android_colors_xml(&dark),
+        );
+        write_file(
+            out_dir,
+            "Color.kt",
+            &compose_color_kt(package, &light, &dark),
+        );
+        write_file(out_dir, "Theme.kt", &compose_theme_kt(package, theme_name));
+    }
+
+    // Check if the 'generate-ios' command was used
+    if let Some(matches) = matches.subcommand_matches("generate-ios") {
+        let core = CorePalette::from_colors(core_palette_colors(matches));
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+
+        let out_dir = matches.get_one::<String>("output").unwrap();
+        for (path, contents) in asset_catalog_files(&light, &dark) {
+            write_file(out_dir, &format!("Colors.xcassets/{}", path), &contents);
+        }
+        if matches.get_flag("swift") {
+            write_file(out_dir, "Color+Theme.swift", &swift_color_extension());
+        }
+    }
+
+    // Check if the 'generate-dart' command was used
+    if let Some(matches) = matches.subcommand_matches("generate-dart") {
+        let core = CorePalette::from_colors(core_palette_colors(matches));
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+        write_file(
+            matches.get_one::<String>("output").unwrap(),
+            "color_schemes.g.dart",
+            &flutter_color_schemes_dart(&light, &dark),
+        );
+    }
+
+    // Check if the 'from-image' command was used
+    if let Some(matches) = matches.subcommand_matches("from-image") {
+        let file = matches.get_one::<String>("file").unwrap();
+        let theme = match theme_from_image(file) {
+            Ok(theme) => theme,
+            Err(error) => {
+                eprintln!("Cannot use '{}': {}, quitting", file, error);
+                std::process::exit(1);
+            }
+        };
+
+        println!("Source color: {}", hex_from_argb(theme.source));
+        for alternate in &theme.alternates {
+            println!("Alternate color: {}", hex_from_argb(*alternate));
+        }
+
+        write_tokens(matches, &theme.palettes, &theme.light, &theme.dark);
+    }
+}
+
+/// The arguments for the colors a core palette is generated from.
+fn core_color_args() -> [Arg; 4] {
+    [
+        Arg::new("primary")
+            .short('p')
+            .long("primary")
+            .required(true)
+            .value_name("#001122")
+            .value_parser(parse_color)
+            .help("Sets the primary color, as a CSS color or hex code, with any alpha last: #RRGGBBAA"),
+        Arg::new("secondary")
+            .short('s')
+            .long("secondary")
+            .value_name("#001122")
+            .value_parser(parse_color)
+            .help("Sets the secondary color, as a CSS color or hex code, with any alpha last: #RRGGBBAA"),
+        Arg::new("tertiary")
+            .short('t')
+            .long("tertiary")
+            .value_name("#001122")
+            .value_parser(parse_color)
+            .help("Sets the tertiary color, as a CSS color or hex code, with any alpha last: #RRGGBBAA"),
+        Arg::new("error")
+            .short('e')
+            .long("error")
+            .value_name("#001122")
+            .value_parser(parse_color)
+            .help("Sets the error color, as a CSS color or hex code, with any alpha last: #RRGGBBAA"),
+    ]
+}
+
+fn output_arg() -> Arg {
+    Arg::new("output")
+        .short('o')
+        .long("output")
+        .required(true)
+        .value_name("DIR")
+        .help("Sets the output directory")
+}
+
+fn format_arg() -> Arg {
+    Arg::new("format")
+        .short('f')
+        .long("format")
+        .value_parser(["css", "dtcg"])
+        .default_value("css")
+        .help("Sets the format: CSS custom properties in tokens.css, or W3C design tokens in tokens.json")
+}
+
+/// Reads the colors passed to `core_color_args`.
+fn core_palette_colors(matches: &ArgMatches) -> CorePaletteColors {
+    CorePaletteColors {
+        primary: Argb(*matches.get_one::<u32>("primary").unwrap()),
+        secondary: matches.get_one::<u32>("secondary").copied().map(Argb),
+        tertiary: matches.get_one::<u32>("tertiary").copied().map(Argb),
+        neutral: None,
+        neutral_variant: None,
+        error: matches.get_one::<u32>("error").copied().map(Argb),
+    }
+}
+
+/// Writes `contents` to the file `name` in `out_dir`.
+fn write_file(out_dir: &str, name: &str, contents: &str) {
+    // Verify we can write to the output directory
+    if !is_directory_writable(out_dir) {
+        println!("Cannot write to '{}', quitting", out_dir);
+        return;
+    }
+
+    let mut path = PathBuf::from(out_dir);
+    path.push(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(&path, contents).unwrap();
+}
+
+/// Writes the tokens of a theme in the format passed to `format_arg`.
+fn write_tokens(matches: &ArgMatches, core: &CorePalette, light: &Scheme, dark: &Scheme) {
+    let out_dir = matches.get_one::<String>("output").unwrap();
+    match matches.get_one::<String>("format").unwrap().as_str() {
+        "dtcg" => write_file(
+            out_dir,
+            "tokens.json",
+            &format!("{}\n", design_tokens(core, light, dark)),
+        ),
+        _ => write_css_tokens(out_dir, light, dark),
+    }
+}
+
+fn write_css_tokens(out_dir: &str, light: &Scheme, dark: &Scheme) {
+    // Verify we can write to the output directory
+    if !is_directory_writable(out_dir) {
+        println!("Cannot write to '{}', quitting", out_dir);
+        return;
+    }
+
+    let mut path = PathBuf::from(out_dir);
+    path.push("tokens.css");
+    let mut file = File::create(&path).unwrap();
+    writeln!(file, ":root {{").unwrap();
+    write_scheme_to_file(light, &mut file, "md-sys-color", "light").unwrap();
+    write_scheme_to_file(dark, &mut file, "md-sys-color", "dark").unwrap();
+    writeln!(file, "}}").unwrap();
+}
+
+/// Parses a CSS color, or a 3 or 6 digit hex code without a leading '#' as
+/// accepted before CSS colors were.
+///
+/// Hex codes with alpha need the '#', and read alpha last, ex. `#11223344`.
+/// Without it, 8 digits used to be read with alpha first, so they are
+/// rejected rather than silently read as a different color.
+fn parse_color(value: &str) -> Result<u32, ColorParseError> {
+    argb_from_css(value).or_else(|error| {
+        if value.trim_start().starts_with('#') {
+            return Err(error);
         }
+        try_argb_from_hex_with_format(value, HexFormat::Rgb).map_err(|hex_error| {
+            // A count of digits is only worth reporting for a hex code.
+            match hex_error.kind {
+                ColorParseErrorKind::InvalidLength(_) => hex_error,
+                _ => error,
+            }
+        })
+    })
+}
+
+/// Kotlin's hard keywords, which can't name a package or a function.
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
 
-        let mut path = PathBuf::from(out_dir);
-        path.push("tokens.css");
-        let mut file = File::create(&path).unwrap();
-        writeln!(file, ":root {{").unwrap();
-        write_scheme_to_file(&light, &mut file, "md-sys-color", "light").unwrap();
-        write_scheme_to_file(&dark, &mut file, "md-sys-color", "dark").unwrap();
-        writeln!(file, "}}").unwrap();
+/// Parses a contrast level, which must be a number from -1.0 to 1.0.
+fn parse_contrast_level(value: &str) -> Result<f64, String> {
+    let level: f64 = value
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", value))?;
+    if level.is_finite() && (-1.0..=1.0).contains(&level) {
+        Ok(level)
+    } else {
+        Err(format!("'{}' is not between -1.0 and 1.0", value))
     }
 }
 
+/// Parses a Kotlin identifier, ex. the name of the theme composable, so that
+/// it can be written into generated Kotlin as is.
+fn parse_kotlin_identifier(value: &str) -> Result<String, String> {
+    let mut chars = value.chars();
+    let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(format!("'{}' is not a Kotlin identifier", value));
+    }
+    if KOTLIN_KEYWORDS.contains(&value) {
+        return Err(format!("'{}' is a Kotlin keyword", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Parses a Kotlin package name, ex. "com.example.ui.theme".
+fn parse_kotlin_package(value: &str) -> Result<String, String> {
+    for name in value.split('.') {
+        parse_kotlin_identifier(name)
+            .map_err(|error| format!("'{}' is not a Kotlin package: {}", value, error))?;
+    }
+    Ok(value.to_string())
+}
+
 fn is_directory_writable(directory: &str) -> bool {
     let metadata = metadata(directory);
     if let Ok(metadata) = metadata {
@@ -115,45 +433,8 @@ fn write_scheme_to_file(
     prefix: &str,
     suffix: &str,
 ) -> std::io::Result<()> {
-    // Iterate over the fields of the Scheme struct
-    for field in &[
-        ("primary", scheme.primary),
-        ("on_primary", scheme.on_primary),
-        ("primary_container", scheme.primary_container),
-        ("on_primary_container", scheme.on_primary_container),
-        ("secondary", scheme.secondary),
-        ("on_secondary", scheme.on_secondary),
-        ("secondary_container", scheme.secondary_container),
-        ("on_secondary_container", scheme.on_secondary_container),
-        ("tertiary", scheme.tertiary),
-        ("on_tertiary", scheme.on_tertiary),
-        ("tertiary_container", scheme.tertiary_container),
-        ("on_tertiary_container", scheme.on_tertiary_container),
-        ("error", scheme.error),
-        ("on_error", scheme.on_error),
-        ("error_container", scheme.error_container),
-        ("on_error_container", scheme.on_error_container),
-        ("surface_dim", scheme.surface_dim),
-        ("surface", scheme.surface),
-        ("surface_bright", scheme.surface_bright),
-        ("surface_container_lowest", scheme.surface_container_lowest),
-        ("surface_container_low", scheme.surface_container_low),
-        ("surface_container", scheme.surface_container),
-        ("surface_container_high", scheme.surface_container_high),
-        (
-            "surface_container_highest",
-            scheme.surface_container_highest,
-        ),
-        ("on_surface", scheme.on_surface),
-        ("on_surface_variant", scheme.on_surface_variant),
-        ("outline", scheme.outline),
-        ("outline_variant", scheme.outline_variant),
-        ("inverse_surface", scheme.inverse_surface),
-        ("inverse_on_surface", scheme.inverse_on_surface),
-        ("inverse_primary", scheme.inverse_primary),
-        ("scrim", scheme.scrim),
-        ("shadow", scheme.shadow),
-    ] {
+    // Iterate over the roles of the Scheme struct
+    for field in scheme.roles() {
         writeln!(
             file,
             "  --{}-{}-{}: {};",
@@ -166,3 +447,68 @@ fn write_scheme_to_file(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_color_reads_hex_with_or_without_hash() {
+        assert_eq!(parse_color("#11223344"), Ok(0x44112233));
+        assert_eq!(parse_color("4285f4"), Ok(0xff4285f4));
+        assert_eq!(parse_color("f00"), Ok(0xffff0000));
+        assert!(parse_color("#4285f").is_err());
+        assert!(parse_color("4285fz").is_err());
+    }
+
+    #[test]
+    fn parse_color_rejects_alpha_without_hash() {
+        // Once read as AARRGGBB, these would now be a different color.
+        for value in ["ff4285f4", "11223344", "f008"] {
+            assert_eq!(
+                parse_color(value).map_err(|error| error.kind),
+                Err(ColorParseErrorKind::InvalidLength(value.len())),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn contrast_level_must_be_between_minus_one_and_one() {
+        assert_eq!(parse_contrast_level("-1"), Ok(-1.0));
+        assert_eq!(parse_contrast_level("0.5"), Ok(0.5));
+        assert_eq!(parse_contrast_level("1.0"), Ok(1.0));
+        for value in ["NaN", "inf", "-inf", "-7", "1.01", "high", ""] {
+            assert!(parse_contrast_level(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn kotlin_names_are_validated() {
+        assert_eq!(
+            parse_kotlin_identifier("AppTheme"),
+            Ok(String::from("AppTheme"))
+        );
+        assert_eq!(
+            parse_kotlin_identifier("_Theme2"),
+            Ok(String::from("_Theme2"))
+        );
+        for name in ["", "2Theme", "App Theme", "App(){}", "`AppTheme`", "fun"] {
+            assert!(parse_kotlin_identifier(name).is_err(), "{name}");
+        }
+
+        assert_eq!(
+            parse_kotlin_package("com.example.ui.theme"),
+            Ok(String::from("com.example.ui.theme"))
+        );
+        for package in [
+            "",
+            "com..example",
+            "com.example.",
+            "com.example;",
+            "com.package",
+        ] {
+            assert!(parse_kotlin_package(package).is_err(), "{package}");
+        }
+    }
+}